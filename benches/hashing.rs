@@ -1,10 +1,11 @@
-use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
 use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::hash::hash_types::RichField;
 use plonky2::hash::hashing::PlonkyPermutation;
 use plonky2::hash::hashing::SPONGE_WIDTH;
+use plonky2::hash::merkle_tree::MerkleTree;
 use plonky2::hash::poseidon::PoseidonPermutation;
-use plonky2_circom_verifier::config::PoseidonBN128Permutation;
+use plonky2_circom_verifier::config::{PoseidonBN128Hash, PoseidonBN128Permutation};
 use std::any::type_name;
 
 pub(crate) fn bench_gl_poseidon<F: RichField>(c: &mut Criterion) {
@@ -33,9 +34,71 @@ pub(crate) fn bench_bn128_poseidon<F: RichField>(c: &mut Criterion) {
     );
 }
 
+pub(crate) fn bench_bn128_poseidon_many<F: RichField>(c: &mut Criterion) {
+    const BATCH: usize = 64;
+    let mut group = c.benchmark_group(format!(
+        "bn128 poseidon<{}, {SPONGE_WIDTH}> x {BATCH}",
+        type_name::<F>()
+    ));
+    group.throughput(Throughput::Elements(BATCH as u64));
+    group.bench_function("one at a time", |b| {
+        b.iter_batched(
+            || vec![F::rand_arr::<SPONGE_WIDTH>(); BATCH],
+            |states| {
+                states
+                    .into_iter()
+                    .map(PoseidonBN128Permutation::permute)
+                    .collect::<Vec<_>>()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("permute_many", |b| {
+        b.iter_batched(
+            || vec![F::rand_arr::<SPONGE_WIDTH>(); BATCH],
+            |mut states| {
+                PoseidonBN128Permutation::permute_many(&mut states);
+                states
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+pub(crate) fn bench_bn128_merkle_tree<F: RichField>(c: &mut Criterion) {
+    const LOG2_LEAVES: usize = 10;
+    const LEAF_LEN: usize = 135;
+    const CAP_HEIGHT: usize = 4;
+    let mut group = c.benchmark_group(format!(
+        "bn128 merkle tree<{}> 2^{LOG2_LEAVES} x {LEAF_LEN}",
+        type_name::<F>()
+    ));
+    group.throughput(Throughput::Elements(1 << LOG2_LEAVES));
+    group.sample_size(10);
+    // MerkleTree::new hashes the subtrees in parallel, the batched tree on one thread.
+    group.bench_function("MerkleTree::new", |b| {
+        b.iter_batched(
+            || vec![F::rand_vec(LEAF_LEN); 1 << LOG2_LEAVES],
+            |leaves| MerkleTree::<F, PoseidonBN128Hash>::new(leaves, CAP_HEIGHT),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("permute_many", |b| {
+        b.iter_batched(
+            || vec![F::rand_vec(LEAF_LEN); 1 << LOG2_LEAVES],
+            |leaves| <PoseidonBN128Hash>::merkle_tree(leaves, CAP_HEIGHT),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     bench_gl_poseidon::<GoldilocksField>(c);
     bench_bn128_poseidon::<GoldilocksField>(c);
+    bench_bn128_poseidon_many::<GoldilocksField>(c);
+    bench_bn128_merkle_tree::<GoldilocksField>(c);
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,8 +1,9 @@
 pragma circom 2.0.9;
 include "./constants.circom";
-include "./poseidon.circom";
+include "./hash.circom";
 
-template GetChallenges() {
+template GetChallengesWithKey() {
+  signal input circuit_digest[4];
   signal input wires_cap[NUM_WIRES_CAP()][4];
   signal input plonk_zs_partial_products_cap[NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP()][4];
   signal input quotient_polys_cap[NUM_QUOTIENT_POLYS_CAP()][4];
@@ -33,11 +34,9 @@ template GetChallenges() {
 
   /// batch 0
   var num_inputs_batch_0 = /* circuit digest */ 4 + /* public input */ 4 + NUM_WIRES_CAP() * 4;
-  var cd[4];
-  cd = CIRCUIT_DIGEST();
-  component observe_batch_0 = HashNoPad_BN(num_inputs_batch_0, SPONGE_WIDTH());
+  component observe_batch_0 = HashNoPad(num_inputs_batch_0, SPONGE_WIDTH());
   for (var i = 0; i < 4; i++) {
-    observe_batch_0.in[i] <== cd[i];
+    observe_batch_0.in[i] <== circuit_digest[i];
   }
   for (var i = 0; i < 4; i++) {
     observe_batch_0.in[i + 4] <== public_input_hash[i];
@@ -61,7 +60,7 @@ template GetChallenges() {
 
   /// batch 1
   var num_inputs_batch_1 = NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP() * 4;
-  component observe_batch_1 = HashNoPad_BN(num_inputs_batch_1 < SPONGE_RATE() ? SPONGE_RATE() : num_inputs_batch_1,
+  component observe_batch_1 = HashNoPad(num_inputs_batch_1 < SPONGE_RATE() ? SPONGE_RATE() : num_inputs_batch_1,
                                            SPONGE_WIDTH());
   for (var i = 0; i < NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP(); i++) {
     for (var j = 0; j < 4; j ++) {
@@ -83,7 +82,7 @@ template GetChallenges() {
 
   /// batch 2
   var num_inputs_batch_2 = NUM_QUOTIENT_POLYS_CAP() * 4;
-  component observe_batch_2 = HashNoPad_BN(num_inputs_batch_2 < SPONGE_RATE() ? SPONGE_RATE() : num_inputs_batch_2,
+  component observe_batch_2 = HashNoPad(num_inputs_batch_2 < SPONGE_RATE() ? SPONGE_RATE() : num_inputs_batch_2,
                                            SPONGE_WIDTH());
   for (var i = 0; i < NUM_QUOTIENT_POLYS_CAP(); i++) {
     for (var j = 0; j < 4; j ++) {
@@ -107,7 +106,7 @@ template GetChallenges() {
   var num_inputs_batch_3 = (NUM_OPENINGS_CONSTANTS() + NUM_OPENINGS_PLONK_SIGMAS() + NUM_OPENINGS_WIRES()
                            + NUM_OPENINGS_PLONK_ZS() + NUM_OPENINGS_PARTIAL_PRODUCTS() + NUM_OPENINGS_QUOTIENT_POLYS()
                            + NUM_OPENINGS_PLONK_ZS_NEXT()) * 2;
-  component observe_batch_3 = HashNoPad_BN(num_inputs_batch_3, SPONGE_WIDTH());
+  component observe_batch_3 = HashNoPad(num_inputs_batch_3, SPONGE_WIDTH());
   var idx = 0;
   for (var i = 0; i < NUM_OPENINGS_CONSTANTS(); i++) {
     observe_batch_3.in[idx] <== openings_constants[i][0];
@@ -156,7 +155,7 @@ template GetChallenges() {
   component observe_batch_4[NUM_FRI_COMMIT_ROUND()];
   for (var round = 0; round < NUM_FRI_COMMIT_ROUND(); round++) {
     var num_inputs = FRI_COMMIT_MERKLE_CAP_HEIGHT() * 4;
-    observe_batch_4[round] = HashNoPad_BN(num_inputs < SPONGE_RATE() ? SPONGE_RATE() : num_inputs, SPONGE_WIDTH());
+    observe_batch_4[round] = HashNoPad(num_inputs < SPONGE_RATE() ? SPONGE_RATE() : num_inputs, SPONGE_WIDTH());
     for (var i = 0; i < FRI_COMMIT_MERKLE_CAP_HEIGHT(); i++) {
       for (var j = 0; j < 4; j ++) {
         observe_batch_4[round].in[i * 4 + j] <== fri_commit_phase_merkle_caps[round][i][j];
@@ -186,7 +185,7 @@ template GetChallenges() {
 
   /// batch 5
   var num_inputs_batch_5 = NUM_FRI_FINAL_POLY_EXT_V() * 2 + 1;
-  component observe_batch_5 = HashNoPad_BN(num_inputs_batch_5, SPONGE_WIDTH());
+  component observe_batch_5 = HashNoPad(num_inputs_batch_5, SPONGE_WIDTH());
   for (var i = 0; i < NUM_FRI_FINAL_POLY_EXT_V(); i++) {
     observe_batch_5.in[i * 2] <== fri_final_poly_ext_v[i][0];
     observe_batch_5.in[i * 2 + 1] <== fri_final_poly_ext_v[i][1];
@@ -211,7 +210,7 @@ template GetChallenges() {
   }
 
   assert(NUM_FRI_QUERY_ROUND() <= 7 + 3 * SPONGE_RATE());
-  component observe_batch_6 = HashNoPad_BN(SPONGE_RATE(), SPONGE_WIDTH());
+  component observe_batch_6 = HashNoPad(SPONGE_RATE(), SPONGE_WIDTH());
   for (var i = 0; i < SPONGE_RATE(); i++) {
     observe_batch_6.in[i] <== observe_batch_5.out[i];
   }
@@ -224,7 +223,7 @@ template GetChallenges() {
     // log(fri_query_indices[i]);
   }
 
-  component observe_batch_7 = HashNoPad_BN(SPONGE_RATE(), SPONGE_WIDTH());
+  component observe_batch_7 = HashNoPad(SPONGE_RATE(), SPONGE_WIDTH());
   if (NUM_FRI_QUERY_ROUND() - 7 > SPONGE_RATE()) {
     for (var i = 0; i < SPONGE_RATE(); i++) {
       observe_batch_7.in[i] <== observe_batch_6.out[i];
@@ -239,7 +238,7 @@ template GetChallenges() {
     }
   }
 
-  component observe_batch_8 = HashNoPad_BN(SPONGE_RATE(), SPONGE_WIDTH());
+  component observe_batch_8 = HashNoPad(SPONGE_RATE(), SPONGE_WIDTH());
   if (NUM_FRI_QUERY_ROUND() - 7 > 2 * SPONGE_RATE()) {
     for (var i = 0; i < SPONGE_RATE(); i++) {
       observe_batch_8.in[i] <== observe_batch_7.out[i];
@@ -254,3 +253,62 @@ template GetChallenges() {
     }
   }
 }
+
+// GetChallengesWithKey for the circuit digest set by the generator.
+template GetChallenges() {
+  signal input wires_cap[NUM_WIRES_CAP()][4];
+  signal input plonk_zs_partial_products_cap[NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP()][4];
+  signal input quotient_polys_cap[NUM_QUOTIENT_POLYS_CAP()][4];
+
+  signal input openings_constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input openings_plonk_sigmas[NUM_OPENINGS_PLONK_SIGMAS()][2];
+  signal input openings_wires[NUM_OPENINGS_WIRES()][2];
+  signal input openings_plonk_zs[NUM_OPENINGS_PLONK_ZS()][2];
+  signal input openings_plonk_zs_next[NUM_OPENINGS_PLONK_ZS_NEXT()][2];
+  signal input openings_partial_products[NUM_OPENINGS_PARTIAL_PRODUCTS()][2];
+  signal input openings_quotient_polys[NUM_OPENINGS_QUOTIENT_POLYS()][2];
+
+  signal input fri_commit_phase_merkle_caps[NUM_FRI_COMMIT_ROUND()][FRI_COMMIT_MERKLE_CAP_HEIGHT()][4];
+  signal input fri_final_poly_ext_v[NUM_FRI_FINAL_POLY_EXT_V()][2];
+  signal input fri_pow_witness;
+  signal input public_input_hash[4];
+
+  signal output plonk_betas[NUM_CHALLENGES()];
+  signal output plonk_gammas[NUM_CHALLENGES()];
+  signal output plonk_alphas[NUM_CHALLENGES()];
+  signal output plonk_zeta[2];
+  signal output fri_alpha[2];
+  signal output fri_betas[NUM_FRI_COMMIT_ROUND()][2];
+  signal output fri_pow_response;
+  signal output fri_query_indices[NUM_FRI_QUERY_ROUND()];
+
+  var cd[4];
+  cd = CIRCUIT_DIGEST();
+  component c = GetChallengesWithKey();
+  for (var i = 0; i < 4; i++) {
+    c.circuit_digest[i] <== cd[i];
+  }
+  c.wires_cap <== wires_cap;
+  c.plonk_zs_partial_products_cap <== plonk_zs_partial_products_cap;
+  c.quotient_polys_cap <== quotient_polys_cap;
+  c.openings_constants <== openings_constants;
+  c.openings_plonk_sigmas <== openings_plonk_sigmas;
+  c.openings_wires <== openings_wires;
+  c.openings_plonk_zs <== openings_plonk_zs;
+  c.openings_plonk_zs_next <== openings_plonk_zs_next;
+  c.openings_partial_products <== openings_partial_products;
+  c.openings_quotient_polys <== openings_quotient_polys;
+  c.fri_commit_phase_merkle_caps <== fri_commit_phase_merkle_caps;
+  c.fri_final_poly_ext_v <== fri_final_poly_ext_v;
+  c.fri_pow_witness <== fri_pow_witness;
+  c.public_input_hash <== public_input_hash;
+
+  plonk_betas <== c.plonk_betas;
+  plonk_gammas <== c.plonk_gammas;
+  plonk_alphas <== c.plonk_alphas;
+  plonk_zeta <== c.plonk_zeta;
+  fri_alpha <== c.fri_alpha;
+  fri_betas <== c.fri_betas;
+  fri_pow_response <== c.fri_pow_response;
+  fri_query_indices <== c.fri_query_indices;
+}
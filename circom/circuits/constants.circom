@@ -143,6 +143,10 @@ function CIRCUIT_DIGEST() {
   cd[3] = 15042019085796853171;
   return cd;
 }
+function VERIFIER_KEY_MODE() { return 0; }
+function RANGE_CHECK_INPUTS() { return 1; }
+function HASHER() { return 0; }
+function PUBLIC_INPUT_HASHER() { return 0; }
 function SPONGE_RATE() { return 8; }
 function SPONGE_CAPACITY() { return 4; }
 function SPONGE_WIDTH() { return 12; }
@@ -1,6 +1,6 @@
 pragma circom 2.0.9;
 include "./constants.circom";
-include "./poseidon.circom";
+include "./hash.circom";
 include "./utils.circom";
 include "./goldilocks.circom";
 include "./goldilocks_ext.circom";
@@ -12,7 +12,7 @@ template GetMerkleProofToCap(nLeaf, nProof) {
   signal output digest[4];
   signal output index;
 
-  component c_digest = HashNoPad_BN(nLeaf, 4);
+  component c_digest = HashNoPad(nLeaf, 4);
   for (var i = 0; i < nLeaf; i++) {
       c_digest.in[i] <== leaf[i];
   }
@@ -20,7 +20,7 @@ template GetMerkleProofToCap(nLeaf, nProof) {
       c_digest.capacity[i] <== 0;
   }
 
-  component poseidon[nProof];
+  component compress[nProof];
   component shift[nProof];
   signal cur_digest[nProof + 1][4];
 
@@ -33,23 +33,23 @@ template GetMerkleProofToCap(nLeaf, nProof) {
   signal in0[nProof][4];
   signal in1[nProof][4];
   for (var i = 0; i < nProof; i++) {
-    poseidon[i] = Poseidon_BN(4);
+    compress[i] = Permutation(4);
 
     for (var j = 0; j < 4; j++) {
       in0[i][j] <== (1 - shift[i].bit) * cur_digest[i][j];
-      poseidon[i].in[j] <== in0[i][j] + shift[i].bit * proof[i][j];
+      compress[i].in[j] <== in0[i][j] + shift[i].bit * proof[i][j];
     }
     for (var j = 0; j < 4; j++) {
       in1[i][j] <== (1 - shift[i].bit) * proof[i][j];
-      poseidon[i].in[j + 4] <== in1[i][j] + shift[i].bit * cur_digest[i][j];
+      compress[i].in[j + 4] <== in1[i][j] + shift[i].bit * cur_digest[i][j];
     }
 
     for (var j = 0; j < 4; j++) {
-      poseidon[i].capacity[j] <== 0;
+      compress[i].capacity[j] <== 0;
     }
 
     for (var j = 0; j < 4; j++) {
-      cur_digest[i + 1][j] <== poseidon[i].out[j];
+      cur_digest[i + 1][j] <== compress[i].out[j];
     }
 
     if (i < nProof - 1) {
@@ -102,7 +102,8 @@ template CalBarycentricWeights(arity) {
   }
 }
 
-template VerifyFriProof() {
+template VerifyFriProofWithKey() {
+  signal input sigma_cap[NUM_SIGMA_CAPS()][4];
   signal input wires_cap[NUM_WIRES_CAP()][4];
   signal input plonk_zs_partial_products_cap[NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP()][4];
   signal input quotient_polys_cap[NUM_QUOTIENT_POLYS_CAP()][4];
@@ -272,12 +273,10 @@ template VerifyFriProof() {
     }
     sigma_caps[round] = RandomAccess2(NUM_SIGMA_CAPS(), 4);
     for (var i = 0; i < NUM_SIGMA_CAPS(); i++) {
-      var cap[4];
-      cap = GET_SIGMA_CAP(i);
-      sigma_caps[round].a[i][0] <== cap[0];
-      sigma_caps[round].a[i][1] <== cap[1];
-      sigma_caps[round].a[i][2] <== cap[2];
-      sigma_caps[round].a[i][3] <== cap[3];
+      sigma_caps[round].a[i][0] <== sigma_cap[i][0];
+      sigma_caps[round].a[i][1] <== sigma_cap[i][1];
+      sigma_caps[round].a[i][2] <== sigma_cap[i][2];
+      sigma_caps[round].a[i][3] <== sigma_cap[i][3];
     }
     sigma_caps[round].idx <== merkle_caps[round][0].index;
     merkle_caps[round][0].digest[0] === sigma_caps[round].out[0];
@@ -714,3 +713,84 @@ template VerifyFriProof() {
     old_eval[round][NUM_REDUCTION_ARITY_BITS()][1] === c_final_eval_add[round][0].out[1];
   }
 }
+
+// VerifyFriProofWithKey for the sigma cap set by the generator.
+template VerifyFriProof() {
+  signal input wires_cap[NUM_WIRES_CAP()][4];
+  signal input plonk_zs_partial_products_cap[NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP()][4];
+  signal input quotient_polys_cap[NUM_QUOTIENT_POLYS_CAP()][4];
+
+  signal input openings_constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input openings_plonk_sigmas[NUM_OPENINGS_PLONK_SIGMAS()][2];
+  signal input openings_wires[NUM_OPENINGS_WIRES()][2];
+  signal input openings_plonk_zs[NUM_OPENINGS_PLONK_ZS()][2];
+  signal input openings_plonk_zs_next[NUM_OPENINGS_PLONK_ZS_NEXT()][2];
+  signal input openings_partial_products[NUM_OPENINGS_PARTIAL_PRODUCTS()][2];
+  signal input openings_quotient_polys[NUM_OPENINGS_QUOTIENT_POLYS()][2];
+
+  signal input fri_commit_phase_merkle_caps[NUM_FRI_COMMIT_ROUND()][FRI_COMMIT_MERKLE_CAP_HEIGHT()][4];
+  signal input fri_query_init_constants_sigmas_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_V()];
+  signal input fri_query_init_constants_sigmas_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_P()][4];
+  signal input fri_query_init_wires_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_WIRES_V()];
+  signal input fri_query_init_wires_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_WIRES_P()][4];
+  signal input fri_query_init_zs_partial_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_ZS_PARTIAL_V()];
+  signal input fri_query_init_zs_partial_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_ZS_PARTIAL_P()][4];
+  signal input fri_query_init_quotient_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_QUOTIENT_V()];
+  signal input fri_query_init_quotient_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_QUOTIENT_P()][4];
+  signal input fri_query_step0_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP0_V()][2];
+  signal input fri_query_step0_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP0_P()][4];
+  signal input fri_query_step1_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP1_V()][2];
+  signal input fri_query_step1_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP1_P()][4];
+  signal input fri_final_poly_ext_v[NUM_FRI_FINAL_POLY_EXT_V()][2];
+
+  // Challenges
+  signal input plonk_zeta[2];
+  signal input fri_alpha[2];
+  signal input fri_betas[NUM_FRI_COMMIT_ROUND()][2];
+  signal input fri_pow_response;
+  signal input fri_query_indices[NUM_FRI_QUERY_ROUND()];
+
+  component c = VerifyFriProofWithKey();
+  for (var i = 0; i < NUM_SIGMA_CAPS(); i++) {
+    var cap[4];
+    cap = GET_SIGMA_CAP(i);
+    c.sigma_cap[i][0] <== cap[0];
+    c.sigma_cap[i][1] <== cap[1];
+    c.sigma_cap[i][2] <== cap[2];
+    c.sigma_cap[i][3] <== cap[3];
+  }
+
+  c.wires_cap <== wires_cap;
+  c.plonk_zs_partial_products_cap <== plonk_zs_partial_products_cap;
+  c.quotient_polys_cap <== quotient_polys_cap;
+
+  c.openings_constants <== openings_constants;
+  c.openings_plonk_sigmas <== openings_plonk_sigmas;
+  c.openings_wires <== openings_wires;
+  c.openings_plonk_zs <== openings_plonk_zs;
+  c.openings_plonk_zs_next <== openings_plonk_zs_next;
+  c.openings_partial_products <== openings_partial_products;
+  c.openings_quotient_polys <== openings_quotient_polys;
+
+  c.fri_commit_phase_merkle_caps <== fri_commit_phase_merkle_caps;
+  c.fri_query_init_constants_sigmas_v <== fri_query_init_constants_sigmas_v;
+  c.fri_query_init_constants_sigmas_p <== fri_query_init_constants_sigmas_p;
+  c.fri_query_init_wires_v <== fri_query_init_wires_v;
+  c.fri_query_init_wires_p <== fri_query_init_wires_p;
+  c.fri_query_init_zs_partial_v <== fri_query_init_zs_partial_v;
+  c.fri_query_init_zs_partial_p <== fri_query_init_zs_partial_p;
+  c.fri_query_init_quotient_v <== fri_query_init_quotient_v;
+  c.fri_query_init_quotient_p <== fri_query_init_quotient_p;
+  c.fri_query_step0_v <== fri_query_step0_v;
+  c.fri_query_step0_p <== fri_query_step0_p;
+  c.fri_query_step1_v <== fri_query_step1_v;
+  c.fri_query_step1_p <== fri_query_step1_p;
+  c.fri_final_poly_ext_v <== fri_final_poly_ext_v;
+
+  // Challenges
+  c.plonk_zeta <== plonk_zeta;
+  c.fri_alpha <== fri_alpha;
+  c.fri_betas <== fri_betas;
+  c.fri_pow_response <== fri_pow_response;
+  c.fri_query_indices <== fri_query_indices;
+}
@@ -18,6 +18,65 @@ template LessNBits(N) {
   x === tmp2[N];
 }
 
+// Verifies x < Order(), so that x is the canonical representative of its Goldilocks element.
+// A 64-bit x is at least Order() iff its high 32 bits are all ones and its low 32 bits are not
+// all zeros.
+template GlCanonical() {
+  signal input x;
+  signal bits[64];
+  signal hi_ones[32];
+  var lo = 0;
+  var hi = 0;
+  var e2 = 1;
+  for (var i = 0; i < 64; i++) {
+    bits[i] <-- (x >> i) & 1;
+    bits[i] * (bits[i] - 1) === 0;
+    if (i < 32) {
+      lo += bits[i] * e2;
+    } else {
+      hi += bits[i] * e2;
+    }
+    e2 = e2 + e2;
+  }
+  x === lo + hi;
+
+  hi_ones[0] <== bits[32];
+  for (var i = 1; i < 32; i++) {
+    hi_ones[i] <== hi_ones[i - 1] * bits[32 + i];
+  }
+  hi_ones[31] * lo === 0;
+}
+
+// The canonical Goldilocks element of the 64-bit word with boolean bits in, least significant
+// first: the word, or the word minus Order() when it is at least Order(). Mirrors reduce_limb in
+// config.rs, which GlReduce(1) does not, since it also accepts the unreduced word.
+template GlReduceBits() {
+  signal input in[64];
+  signal output out;
+  signal hi_ones[32];
+  var lo = 0;
+  var hi = 0;
+  var e2 = 1;
+  for (var i = 0; i < 64; i++) {
+    if (i < 32) {
+      lo += in[i] * e2;
+    } else {
+      hi += in[i] * e2;
+    }
+    e2 = e2 + e2;
+  }
+
+  hi_ones[0] <== in[32];
+  for (var i = 1; i < 32; i++) {
+    hi_ones[i] <== hi_ones[i - 1] * in[32 + i];
+  }
+  signal lo_inv <-- lo != 0 ? 1 / lo : 0;
+  signal lo_zero <== 1 - lo * lo_inv;
+  lo * lo_zero === 0;
+  signal ge <== hi_ones[31] * (1 - lo_zero);
+  out <== lo + hi - ge * Order();
+}
+
 // Gl: Goldilocks
 // range check d < 1 << N
 template GlReduce(N) {
@@ -0,0 +1,137 @@
+pragma circom 2.0.9;
+include "./constants.circom";
+include "./poseidon.circom";
+include "./keccak.circom";
+include "./sha256.circom";
+
+// Sponge permutation of the hasher of the verified proof, selected by HASHER().
+template Permutation(nOuts) {
+  signal input in[8];
+  signal input capacity[4];
+  signal output out[nOuts];
+
+  component p = PermutationOf(HASHER(), nOuts);
+  for (var i = 0; i < 8; i++) {
+    p.in[i] <== in[i];
+  }
+  for (var i = 0; i < 4; i++) {
+    p.capacity[i] <== capacity[i];
+  }
+  for (var i = 0; i < nOuts; i++) {
+    out[i] <== p.out[i];
+  }
+}
+
+// Sponge permutation of CircomHasherKind hasher in Rust.
+template PermutationOf(hasher, nOuts) {
+  signal input in[8];
+  signal input capacity[4];
+  signal output out[nOuts];
+
+  component p;
+  if (hasher == 0) {
+    p = Poseidon_BN(nOuts);
+  } else if (hasher == 1) {
+    p = Keccak_GL(nOuts);
+  } else {
+    assert(hasher == 2);
+    p = Sha256_GL(nOuts);
+  }
+  for (var i = 0; i < 8; i++) {
+    p.in[i] <== in[i];
+  }
+  for (var i = 0; i < 4; i++) {
+    p.capacity[i] <== capacity[i];
+  }
+  for (var i = 0; i < nOuts; i++) {
+    out[i] <== p.out[i];
+  }
+}
+
+// HashNoPad_BN with the permutation selected by HASHER().
+template HashNoPad(nInputs, nOutputs) {
+    signal input in[nInputs];
+    signal input capacity[4];
+    signal output out[nOutputs];
+
+    component h = HashNoPadOf(HASHER(), nInputs, nOutputs);
+    for (var i = 0; i < nInputs; i++) {
+        h.in[i] <== in[i];
+    }
+    for (var i = 0; i < 4; i++) {
+        h.capacity[i] <== capacity[i];
+    }
+    for (var i = 0; i < nOutputs; i++) {
+        out[i] <== h.out[i];
+    }
+}
+
+// HashNoPad_BN with the permutation of CircomHasherKind hasher in Rust.
+template HashNoPadOf(hasher, nInputs, nOutputs) {
+    signal input in[nInputs];
+    signal input capacity[4];
+    signal output out[nOutputs];
+    assert(nOutputs <= 12);
+
+    var nHash = (nInputs + 7) \ 8;
+    component cPermutation[nHash];
+
+    for (var i = 0; i < nHash; i++) {
+        cPermutation[i] = PermutationOf(hasher, 12);
+    }
+    cPermutation[0].capacity[0] <== capacity[0];
+    cPermutation[0].capacity[1] <== capacity[1];
+    cPermutation[0].capacity[2] <== capacity[2];
+    cPermutation[0].capacity[3] <== capacity[3];
+
+    for (var i = 0; i < nHash; i++) {
+        for (var j = 0; j < 8; j++) {
+            var index = i * 8 + j;
+            if (index >= nInputs) {
+                if (i > 0) {
+                  cPermutation[i].in[j] <== cPermutation[i-1].out[j];
+                } else {
+                  cPermutation[i].in[j] <== 0;
+                }
+            } else {
+                cPermutation[i].in[j] <== in[index];
+            }
+        }
+        if (i > 0) {
+            cPermutation[i].capacity[0] <== cPermutation[i-1].out[8];
+            cPermutation[i].capacity[1] <== cPermutation[i-1].out[9];
+            cPermutation[i].capacity[2] <== cPermutation[i-1].out[10];
+            cPermutation[i].capacity[3] <== cPermutation[i-1].out[11];
+        }
+    }
+
+    for (var i = 0; i < nOutputs; i++) {
+        out[i] <== cPermutation[nHash - 1].out[i];
+    }
+}
+
+// Hash of the public inputs, like Hasher::hash_public_inputs in Rust, with the hasher selected
+// by PUBLIC_INPUT_HASHER(), the CircomPublicInputHasherKind in Rust.
+template HashPublicInputs(nInputs) {
+  signal input in[nInputs];
+  signal output out[4];
+
+  component h;
+  if (PUBLIC_INPUT_HASHER() == 0) {
+    h = HashNoPad_GL(nInputs, 4);
+  } else if (PUBLIC_INPUT_HASHER() == 1) {
+    h = HashNoPadOf(0, nInputs, 4);
+  } else {
+    assert(PUBLIC_INPUT_HASHER() == 2);
+    h = HashNoPadOf(1, nInputs, 4);
+  }
+  for (var i = 0; i < nInputs; i++) {
+    h.in[i] <== in[i];
+  }
+  for (var i = 0; i < 4; i++) {
+    h.capacity[i] <== 0;
+  }
+  for (var i = 0; i < 4; i++) {
+    out[i] <== h.out[i];
+  }
+}
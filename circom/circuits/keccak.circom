@@ -0,0 +1,155 @@
+pragma circom 2.0.9;
+include "../node_modules/circomlib/circuits/bitify.circom";
+include "./goldilocks.circom";
+
+// One round of Keccak-f[1600] on 25 lanes x + 5 * y of 64 bits, least significant bit first.
+template KeccakRound(rc) {
+  signal input in[25][64];
+  signal output out[25][64];
+
+  var ROT[25] = [
+     0,  1, 62, 28, 27,
+    36, 44,  6, 55, 20,
+     3, 10, 43, 25, 39,
+    41, 45, 15, 21,  8,
+    18,  2, 61, 56, 14
+  ];
+
+  // theta
+  signal parity[5][4][64];
+  for (var x = 0; x < 5; x++) {
+    for (var z = 0; z < 64; z++) {
+      parity[x][0][z] <== in[x][z] + in[x + 5][z] - 2 * in[x][z] * in[x + 5][z];
+      for (var k = 1; k < 4; k++) {
+        var a = in[x + 5 * (k + 1)][z];
+        parity[x][k][z] <== parity[x][k - 1][z] + a - 2 * parity[x][k - 1][z] * a;
+      }
+    }
+  }
+  signal d[5][64];
+  for (var x = 0; x < 5; x++) {
+    for (var z = 0; z < 64; z++) {
+      var l = parity[(x + 4) % 5][3][z];
+      var r = parity[(x + 1) % 5][3][(z + 63) % 64];
+      d[x][z] <== l + r - 2 * l * r;
+    }
+  }
+  signal theta[25][64];
+  for (var i = 0; i < 25; i++) {
+    for (var z = 0; z < 64; z++) {
+      theta[i][z] <== in[i][z] + d[i % 5][z] - 2 * in[i][z] * d[i % 5][z];
+    }
+  }
+
+  // rho and pi only move bits: b[j] is theta[src[j]] rotated left by ROT[src[j]].
+  var src[25];
+  for (var x = 0; x < 5; x++) {
+    for (var y = 0; y < 5; y++) {
+      src[y + 5 * ((2 * x + 3 * y) % 5)] = x + 5 * y;
+    }
+  }
+
+  // chi and iota
+  signal t[25][64];
+  for (var i = 0; i < 25; i++) {
+    var row = i - i % 5;
+    var s0 = src[i];
+    var s1 = src[row + (i + 1) % 5];
+    var s2 = src[row + (i + 2) % 5];
+    for (var z = 0; z < 64; z++) {
+      var b0 = theta[s0][(z + 64 - ROT[s0]) % 64];
+      var b1 = theta[s1][(z + 64 - ROT[s1]) % 64];
+      var b2 = theta[s2][(z + 64 - ROT[s2]) % 64];
+      t[i][z] <== (1 - b1) * b2;
+      if (i == 0 && ((rc >> z) & 1) == 1) {
+        out[i][z] <== 1 - (b0 + t[i][z] - 2 * b0 * t[i][z]);
+      } else {
+        out[i][z] <== b0 + t[i][z] - 2 * b0 * t[i][z];
+      }
+    }
+  }
+}
+
+template KeccakF() {
+  signal input in[25][64];
+  signal output out[25][64];
+
+  var RC[24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008
+  ];
+
+  component rounds[24];
+  for (var r = 0; r < 24; r++) {
+    rounds[r] = KeccakRound(RC[r]);
+    for (var i = 0; i < 25; i++) {
+      for (var z = 0; z < 64; z++) {
+        if (r == 0) {
+          rounds[r].in[i][z] <== in[i][z];
+        } else {
+          rounds[r].in[i][z] <== rounds[r - 1].out[i][z];
+        }
+      }
+    }
+  }
+  for (var i = 0; i < 25; i++) {
+    for (var z = 0; z < 64; z++) {
+      out[i][z] <== rounds[23].out[i][z];
+    }
+  }
+}
+
+// Keccak256Permutation of plonky2_circom_verifier::config. Output k * 4 + j is the j-th
+// little-endian 64-bit word of keccak256(state || k), reduced to the Goldilocks field.
+template Keccak_GL(nOuts) {
+  signal input in[8];
+  signal input capacity[4];
+  signal output out[nOuts];
+  assert(nOuts <= 12);
+
+  component n2b[12];
+  for (var i = 0; i < 12; i++) {
+    n2b[i] = Num2Bits(64);
+    if (i < 8) {
+      n2b[i].in <== in[i];
+    } else {
+      n2b[i].in <== capacity[i - 8];
+    }
+  }
+
+  // The 97 byte message fits in one block of 17 lanes: the state in lanes 0..11, then byte k
+  // and the 0x01 .. 0x80 padding.
+  var nDigests = (nOuts + 3) \ 4;
+  component f[nDigests];
+  component reduce[nOuts];
+  for (var k = 0; k < nDigests; k++) {
+    f[k] = KeccakF();
+    for (var i = 0; i < 25; i++) {
+      var lane = 0;
+      if (i == 12) {
+        lane = k + 0x100;
+      }
+      if (i == 16) {
+        lane = 1 << 63;
+      }
+      for (var z = 0; z < 64; z++) {
+        if (i < 12) {
+          f[k].in[i][z] <== n2b[i].out[z];
+        } else {
+          f[k].in[i][z] <== (lane >> z) & 1;
+        }
+      }
+    }
+    for (var j = 0; j < 4 && k * 4 + j < nOuts; j++) {
+      reduce[k * 4 + j] = GlReduceBits();
+      for (var z = 0; z < 64; z++) {
+        reduce[k * 4 + j].in[z] <== f[k].out[j][z];
+      }
+      out[k * 4 + j] <== reduce[k * 4 + j].out;
+    }
+  }
+}
@@ -0,0 +1,88 @@
+pragma circom 2.1.0;
+include "./verifier.circom";
+
+// VerifyPlonky2ProofWithKey(2), which outputs the BN128 Poseidon hash of the private verifier
+// key, computed as circom_verifier_key_hash in Rust.
+template VerifyPlonky2ProofWithKeyHash() {
+  signal input circuit_digest[4];
+  signal input sigma_cap[NUM_SIGMA_CAPS()][4];
+  signal input wires_cap[NUM_WIRES_CAP()][4];
+  signal input plonk_zs_partial_products_cap[NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP()][4];
+  signal input quotient_polys_cap[NUM_QUOTIENT_POLYS_CAP()][4];
+
+  signal input openings_constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input openings_plonk_sigmas[NUM_OPENINGS_PLONK_SIGMAS()][2];
+  signal input openings_wires[NUM_OPENINGS_WIRES()][2];
+  signal input openings_plonk_zs[NUM_OPENINGS_PLONK_ZS()][2];
+  signal input openings_plonk_zs_next[NUM_OPENINGS_PLONK_ZS_NEXT()][2];
+  signal input openings_partial_products[NUM_OPENINGS_PARTIAL_PRODUCTS()][2];
+  signal input openings_quotient_polys[NUM_OPENINGS_QUOTIENT_POLYS()][2];
+
+  signal input fri_commit_phase_merkle_caps[NUM_FRI_COMMIT_ROUND()][FRI_COMMIT_MERKLE_CAP_HEIGHT()][4];
+  signal input fri_query_init_constants_sigmas_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_V()];
+  signal input fri_query_init_constants_sigmas_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_P()][4];
+  signal input fri_query_init_wires_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_WIRES_V()];
+  signal input fri_query_init_wires_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_WIRES_P()][4];
+  signal input fri_query_init_zs_partial_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_ZS_PARTIAL_V()];
+  signal input fri_query_init_zs_partial_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_ZS_PARTIAL_P()][4];
+  signal input fri_query_init_quotient_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_QUOTIENT_V()];
+  signal input fri_query_init_quotient_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_QUOTIENT_P()][4];
+  signal input fri_query_step0_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP0_V()][2];
+  signal input fri_query_step0_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP0_P()][4];
+  signal input fri_query_step1_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP1_V()][2];
+  signal input fri_query_step1_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP1_P()][4];
+  signal input fri_final_poly_ext_v[NUM_FRI_FINAL_POLY_EXT_V()][2];
+  signal input fri_pow_witness;
+  signal input public_inputs[NUM_PUBLIC_INPUTS()];
+
+  signal output verifier_key_hash[4];
+
+  component c = VerifyPlonky2ProofWithKey(2);
+  c.circuit_digest <== circuit_digest;
+  c.sigma_cap <== sigma_cap;
+
+  c.wires_cap <== wires_cap;
+  c.plonk_zs_partial_products_cap <== plonk_zs_partial_products_cap;
+  c.quotient_polys_cap <== quotient_polys_cap;
+
+  c.openings_constants <== openings_constants;
+  c.openings_plonk_sigmas <== openings_plonk_sigmas;
+  c.openings_wires <== openings_wires;
+  c.openings_plonk_zs <== openings_plonk_zs;
+  c.openings_plonk_zs_next <== openings_plonk_zs_next;
+  c.openings_partial_products <== openings_partial_products;
+  c.openings_quotient_polys <== openings_quotient_polys;
+
+  c.fri_commit_phase_merkle_caps <== fri_commit_phase_merkle_caps;
+  c.fri_query_init_constants_sigmas_v <== fri_query_init_constants_sigmas_v;
+  c.fri_query_init_constants_sigmas_p <== fri_query_init_constants_sigmas_p;
+  c.fri_query_init_wires_v <== fri_query_init_wires_v;
+  c.fri_query_init_wires_p <== fri_query_init_wires_p;
+  c.fri_query_init_zs_partial_v <== fri_query_init_zs_partial_v;
+  c.fri_query_init_zs_partial_p <== fri_query_init_zs_partial_p;
+  c.fri_query_init_quotient_v <== fri_query_init_quotient_v;
+  c.fri_query_init_quotient_p <== fri_query_init_quotient_p;
+  c.fri_query_step0_v <== fri_query_step0_v;
+  c.fri_query_step0_p <== fri_query_step0_p;
+  c.fri_query_step1_v <== fri_query_step1_v;
+  c.fri_query_step1_p <== fri_query_step1_p;
+  c.fri_final_poly_ext_v <== fri_final_poly_ext_v;
+  c.fri_pow_witness <== fri_pow_witness;
+  c.public_inputs <== public_inputs;
+
+  component hasher = HashNoPad_BN(4 + NUM_SIGMA_CAPS() * 4, 4);
+  for (var i = 0; i < 4; i++) {
+    hasher.in[i] <== circuit_digest[i];
+  }
+  for (var i = 0; i < NUM_SIGMA_CAPS(); i++) {
+    for (var j = 0; j < 4; j++) {
+      hasher.in[4 + i * 4 + j] <== sigma_cap[i][j];
+    }
+  }
+  for (var i = 0; i < 4; i++) {
+    hasher.capacity[i] <== 0;
+  }
+  verifier_key_hash <== hasher.out;
+}
+
+component main {public [public_inputs]} = VerifyPlonky2ProofWithKeyHash();
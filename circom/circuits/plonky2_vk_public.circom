@@ -0,0 +1,4 @@
+pragma circom 2.1.0;
+include "./verifier.circom";
+
+component main {public [public_inputs, circuit_digest, sigma_cap]} = VerifyPlonky2ProofWithKey(1);
@@ -2,6 +2,7 @@ pragma circom 2.0.6;
 include "./goldilocks.circom";
 include "../node_modules/circomlib/circuits/poseidon.circom";
 include "../node_modules/circomlib/circuits/bitify.circom";
+include "../node_modules/circomlib/circuits/comparators.circom";
 
 function GL_CONST(i) {
     var const[30*12] = [
@@ -224,11 +225,13 @@ template Poseidon_BN(nOuts) {
     pEx.inputs[2] <== in[6] * 2 ** 128 + in[7] * 2 ** 64 + capacity[0];
     pEx.inputs[3] <== capacity[1] * 2 ** 128 + capacity[2] * 2 ** 64 + capacity[3];
 
+    // Num2Bits(254) alone would also accept the bits of out + p when it is below 2^254, which
+    // splits into different limbs, so the decomposition must be the canonical one.
     component nBits[4];
     signal gl_hashes[12][64];
     var e2;
     for (var i = 0; i < 4; i++) {
-      nBits[i] = Num2Bits(254);
+      nBits[i] = Num2Bits_strict();
       nBits[i].in <== pEx.out[i];
       for (var j = 0; j < 3; j++) {
         gl_hashes[i * 3 + j][0] <== nBits[i].out[(2 - j) * 64];
@@ -240,8 +243,29 @@ template Poseidon_BN(nOuts) {
       }
     }
 
+    // A 64-bit limb x is at least Order() iff its high 32 bits are all ones and its low 32 bits
+    // are not all zeros, in which case x - Order() is canonical. Mirrors
+    // poseidon_bn128::goldilocks_limbs.
+    signal hi_ones[nOuts][32];
+    signal ge[nOuts];
+    component lo_zero[nOuts];
+    var lo;
     for (var i = 0; i < nOuts; i++) {
-      out[i] <== gl_hashes[i][63];
+      var bits = (2 - i % 3) * 64;
+      hi_ones[i][0] <== nBits[i \ 3].out[bits + 32];
+      for (var k = 1; k < 32; k++) {
+        hi_ones[i][k] <== hi_ones[i][k - 1] * nBits[i \ 3].out[bits + 32 + k];
+      }
+      lo = 0;
+      e2 = 1;
+      for (var k = 0; k < 32; k++) {
+        lo += nBits[i \ 3].out[bits + k] * e2;
+        e2 = e2 + e2;
+      }
+      lo_zero[i] = IsZero();
+      lo_zero[i].in <== lo;
+      ge[i] <== hi_ones[i][31] * (1 - lo_zero[i].out);
+      out[i] <== gl_hashes[i][63] - ge[i] * Order();
     }
 }
 
@@ -284,11 +308,8 @@ template HashNoPad_BN(nInputs, nOutputs) {
         }
     }
 
-    component cGlReduce[nOutputs];
     for (var i = 0; i < nOutputs; i++) {
-        cGlReduce[i] = GlReduce(1);
-        cGlReduce[i].x <== cPoseidon[nHash - 1].out[i];
-        out[i] <== cGlReduce[i].out;
+        out[i] <== cPoseidon[nHash - 1].out[i];
     }
 }
 
@@ -0,0 +1,51 @@
+pragma circom 2.0.9;
+include "../node_modules/circomlib/circuits/bitify.circom";
+include "../node_modules/circomlib/circuits/sha256/sha256.circom";
+include "./goldilocks.circom";
+
+// Sha256Permutation of plonky2_circom_verifier::config. Output k * 4 + j is the j-th
+// little-endian 64-bit word of sha256(state || k), reduced to the Goldilocks field.
+template Sha256_GL(nOuts) {
+  signal input in[8];
+  signal input capacity[4];
+  signal output out[nOuts];
+  assert(nOuts <= 12);
+
+  component n2b[12];
+  for (var i = 0; i < 12; i++) {
+    n2b[i] = Num2Bits(64);
+    if (i < 8) {
+      n2b[i].in <== in[i];
+    } else {
+      n2b[i].in <== capacity[i - 8];
+    }
+  }
+
+  // Sha256 takes the bits of each byte most significant first, while the state words are
+  // serialized as little-endian bytes.
+  var nDigests = (nOuts + 3) \ 4;
+  component sha[nDigests];
+  component reduce[nOuts];
+  for (var k = 0; k < nDigests; k++) {
+    sha[k] = Sha256(97 * 8);
+    for (var i = 0; i < 12; i++) {
+      for (var b = 0; b < 8; b++) {
+        for (var z = 0; z < 8; z++) {
+          sha[k].in[(i * 8 + b) * 8 + 7 - z] <== n2b[i].out[b * 8 + z];
+        }
+      }
+    }
+    for (var z = 0; z < 8; z++) {
+      sha[k].in[96 * 8 + 7 - z] <== (k >> z) & 1;
+    }
+    for (var j = 0; j < 4 && k * 4 + j < nOuts; j++) {
+      reduce[k * 4 + j] = GlReduceBits();
+      for (var b = 0; b < 8; b++) {
+        for (var z = 0; z < 8; z++) {
+          reduce[k * 4 + j].in[b * 8 + z] <== sha[k].out[(j * 8 + b) * 8 + 7 - z];
+        }
+      }
+      out[k * 4 + j] <== reduce[k * 4 + j].out;
+    }
+  }
+}
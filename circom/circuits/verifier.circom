@@ -0,0 +1,464 @@
+pragma circom 2.1.0;
+include "./challenges.circom";
+include "./plonk.circom";
+include "./fri.circom";
+
+// Number of values CanonicalProofInputs checks.
+function NUM_PROOF_INPUTS(with_key) {
+  var n = 4 * (NUM_WIRES_CAP() + NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP() + NUM_QUOTIENT_POLYS_CAP());
+  n += 2 * (NUM_OPENINGS_CONSTANTS() + NUM_OPENINGS_PLONK_SIGMAS() + NUM_OPENINGS_WIRES() +
+            NUM_OPENINGS_PLONK_ZS() + NUM_OPENINGS_PLONK_ZS_NEXT() +
+            NUM_OPENINGS_PARTIAL_PRODUCTS() + NUM_OPENINGS_QUOTIENT_POLYS());
+  n += 4 * NUM_FRI_COMMIT_ROUND() * FRI_COMMIT_MERKLE_CAP_HEIGHT();
+  n += NUM_FRI_QUERY_ROUND() * (
+    NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_V() + 4 * NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_P() +
+    NUM_FRI_QUERY_INIT_WIRES_V() + 4 * NUM_FRI_QUERY_INIT_WIRES_P() +
+    NUM_FRI_QUERY_INIT_ZS_PARTIAL_V() + 4 * NUM_FRI_QUERY_INIT_ZS_PARTIAL_P() +
+    NUM_FRI_QUERY_INIT_QUOTIENT_V() + 4 * NUM_FRI_QUERY_INIT_QUOTIENT_P() +
+    2 * NUM_FRI_QUERY_STEP0_V() + 4 * NUM_FRI_QUERY_STEP0_P() +
+    2 * NUM_FRI_QUERY_STEP1_V() + 4 * NUM_FRI_QUERY_STEP1_P());
+  n += 2 * NUM_FRI_FINAL_POLY_EXT_V() + 1 + NUM_PUBLIC_INPUTS();
+  if (with_key == 1) {
+    n += 4 + 4 * NUM_SIGMA_CAPS();
+  }
+  return n;
+}
+
+// Checks that every input is the canonical representative of its Goldilocks element. The
+// templates reduce their inputs modulo Order(), so without it values that only agree modulo
+// Order() would be accepted for the same proof. with_key also checks the verifier key, which
+// is a constant when VERIFIER_KEY_MODE() is 0.
+template CanonicalProofInputs(with_key) {
+  signal input circuit_digest[4];
+  signal input sigma_cap[NUM_SIGMA_CAPS()][4];
+  signal input wires_cap[NUM_WIRES_CAP()][4];
+  signal input plonk_zs_partial_products_cap[NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP()][4];
+  signal input quotient_polys_cap[NUM_QUOTIENT_POLYS_CAP()][4];
+
+  signal input openings_constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input openings_plonk_sigmas[NUM_OPENINGS_PLONK_SIGMAS()][2];
+  signal input openings_wires[NUM_OPENINGS_WIRES()][2];
+  signal input openings_plonk_zs[NUM_OPENINGS_PLONK_ZS()][2];
+  signal input openings_plonk_zs_next[NUM_OPENINGS_PLONK_ZS_NEXT()][2];
+  signal input openings_partial_products[NUM_OPENINGS_PARTIAL_PRODUCTS()][2];
+  signal input openings_quotient_polys[NUM_OPENINGS_QUOTIENT_POLYS()][2];
+
+  signal input fri_commit_phase_merkle_caps[NUM_FRI_COMMIT_ROUND()][FRI_COMMIT_MERKLE_CAP_HEIGHT()][4];
+  signal input fri_query_init_constants_sigmas_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_V()];
+  signal input fri_query_init_constants_sigmas_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_P()][4];
+  signal input fri_query_init_wires_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_WIRES_V()];
+  signal input fri_query_init_wires_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_WIRES_P()][4];
+  signal input fri_query_init_zs_partial_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_ZS_PARTIAL_V()];
+  signal input fri_query_init_zs_partial_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_ZS_PARTIAL_P()][4];
+  signal input fri_query_init_quotient_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_QUOTIENT_V()];
+  signal input fri_query_init_quotient_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_QUOTIENT_P()][4];
+  signal input fri_query_step0_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP0_V()][2];
+  signal input fri_query_step0_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP0_P()][4];
+  signal input fri_query_step1_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP1_V()][2];
+  signal input fri_query_step1_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP1_P()][4];
+  signal input fri_final_poly_ext_v[NUM_FRI_FINAL_POLY_EXT_V()][2];
+  signal input fri_pow_witness;
+  signal input public_inputs[NUM_PUBLIC_INPUTS()];
+
+  component c[NUM_PROOF_INPUTS(with_key)];
+  var k = 0;
+
+  if (with_key == 1) {
+    for (var i = 0; i < 4; i++) {
+      c[k] = GlCanonical();
+      c[k].x <== circuit_digest[i];
+      k++;
+    }
+    for (var i = 0; i < NUM_SIGMA_CAPS(); i++) {
+      for (var j = 0; j < 4; j++) {
+        c[k] = GlCanonical();
+        c[k].x <== sigma_cap[i][j];
+        k++;
+      }
+    }
+  }
+
+  for (var i = 0; i < NUM_WIRES_CAP(); i++) {
+    for (var j = 0; j < 4; j++) {
+      c[k] = GlCanonical();
+      c[k].x <== wires_cap[i][j];
+      k++;
+    }
+  }
+  for (var i = 0; i < NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP(); i++) {
+    for (var j = 0; j < 4; j++) {
+      c[k] = GlCanonical();
+      c[k].x <== plonk_zs_partial_products_cap[i][j];
+      k++;
+    }
+  }
+  for (var i = 0; i < NUM_QUOTIENT_POLYS_CAP(); i++) {
+    for (var j = 0; j < 4; j++) {
+      c[k] = GlCanonical();
+      c[k].x <== quotient_polys_cap[i][j];
+      k++;
+    }
+  }
+
+  for (var j = 0; j < 2; j++) {
+    for (var i = 0; i < NUM_OPENINGS_CONSTANTS(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== openings_constants[i][j];
+      k++;
+    }
+    for (var i = 0; i < NUM_OPENINGS_PLONK_SIGMAS(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== openings_plonk_sigmas[i][j];
+      k++;
+    }
+    for (var i = 0; i < NUM_OPENINGS_WIRES(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== openings_wires[i][j];
+      k++;
+    }
+    for (var i = 0; i < NUM_OPENINGS_PLONK_ZS(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== openings_plonk_zs[i][j];
+      k++;
+    }
+    for (var i = 0; i < NUM_OPENINGS_PLONK_ZS_NEXT(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== openings_plonk_zs_next[i][j];
+      k++;
+    }
+    for (var i = 0; i < NUM_OPENINGS_PARTIAL_PRODUCTS(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== openings_partial_products[i][j];
+      k++;
+    }
+    for (var i = 0; i < NUM_OPENINGS_QUOTIENT_POLYS(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== openings_quotient_polys[i][j];
+      k++;
+    }
+    for (var i = 0; i < NUM_FRI_FINAL_POLY_EXT_V(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== fri_final_poly_ext_v[i][j];
+      k++;
+    }
+  }
+
+  for (var i = 0; i < NUM_FRI_COMMIT_ROUND(); i++) {
+    for (var j = 0; j < FRI_COMMIT_MERKLE_CAP_HEIGHT(); j++) {
+      for (var l = 0; l < 4; l++) {
+        c[k] = GlCanonical();
+        c[k].x <== fri_commit_phase_merkle_caps[i][j][l];
+        k++;
+      }
+    }
+  }
+
+  for (var r = 0; r < NUM_FRI_QUERY_ROUND(); r++) {
+    for (var i = 0; i < NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_V(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== fri_query_init_constants_sigmas_v[r][i];
+      k++;
+    }
+    for (var i = 0; i < NUM_FRI_QUERY_INIT_WIRES_V(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== fri_query_init_wires_v[r][i];
+      k++;
+    }
+    for (var i = 0; i < NUM_FRI_QUERY_INIT_ZS_PARTIAL_V(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== fri_query_init_zs_partial_v[r][i];
+      k++;
+    }
+    for (var i = 0; i < NUM_FRI_QUERY_INIT_QUOTIENT_V(); i++) {
+      c[k] = GlCanonical();
+      c[k].x <== fri_query_init_quotient_v[r][i];
+      k++;
+    }
+    for (var j = 0; j < 2; j++) {
+      for (var i = 0; i < NUM_FRI_QUERY_STEP0_V(); i++) {
+        c[k] = GlCanonical();
+        c[k].x <== fri_query_step0_v[r][i][j];
+        k++;
+      }
+      for (var i = 0; i < NUM_FRI_QUERY_STEP1_V(); i++) {
+        c[k] = GlCanonical();
+        c[k].x <== fri_query_step1_v[r][i][j];
+        k++;
+      }
+    }
+    for (var l = 0; l < 4; l++) {
+      for (var i = 0; i < NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_P(); i++) {
+        c[k] = GlCanonical();
+        c[k].x <== fri_query_init_constants_sigmas_p[r][i][l];
+        k++;
+      }
+      for (var i = 0; i < NUM_FRI_QUERY_INIT_WIRES_P(); i++) {
+        c[k] = GlCanonical();
+        c[k].x <== fri_query_init_wires_p[r][i][l];
+        k++;
+      }
+      for (var i = 0; i < NUM_FRI_QUERY_INIT_ZS_PARTIAL_P(); i++) {
+        c[k] = GlCanonical();
+        c[k].x <== fri_query_init_zs_partial_p[r][i][l];
+        k++;
+      }
+      for (var i = 0; i < NUM_FRI_QUERY_INIT_QUOTIENT_P(); i++) {
+        c[k] = GlCanonical();
+        c[k].x <== fri_query_init_quotient_p[r][i][l];
+        k++;
+      }
+      for (var i = 0; i < NUM_FRI_QUERY_STEP0_P(); i++) {
+        c[k] = GlCanonical();
+        c[k].x <== fri_query_step0_p[r][i][l];
+        k++;
+      }
+      for (var i = 0; i < NUM_FRI_QUERY_STEP1_P(); i++) {
+        c[k] = GlCanonical();
+        c[k].x <== fri_query_step1_p[r][i][l];
+        k++;
+      }
+    }
+  }
+
+  c[k] = GlCanonical();
+  c[k].x <== fri_pow_witness;
+  k++;
+  for (var i = 0; i < NUM_PUBLIC_INPUTS(); i++) {
+    c[k] = GlCanonical();
+    c[k].x <== public_inputs[i];
+    k++;
+  }
+  assert(k == NUM_PROOF_INPUTS(with_key));
+}
+
+// Verifies a proof against a verifier key given as inputs. verifier_key_mode is the
+// VERIFIER_KEY_MODE() the constants were generated for: 0 for a key baked into the constants,
+// 1 for a public key and 2 for a key only committed to by the public verifier_key_hash of
+// VerifyPlonky2ProofWithKeyHash.
+template VerifyPlonky2ProofWithKey(verifier_key_mode) {
+  assert(VERIFIER_KEY_MODE() == verifier_key_mode);
+
+  signal input circuit_digest[4];
+  signal input sigma_cap[NUM_SIGMA_CAPS()][4];
+  signal input wires_cap[NUM_WIRES_CAP()][4];
+  signal input plonk_zs_partial_products_cap[NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP()][4];
+  signal input quotient_polys_cap[NUM_QUOTIENT_POLYS_CAP()][4];
+
+  signal input openings_constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input openings_plonk_sigmas[NUM_OPENINGS_PLONK_SIGMAS()][2];
+  signal input openings_wires[NUM_OPENINGS_WIRES()][2];
+  signal input openings_plonk_zs[NUM_OPENINGS_PLONK_ZS()][2];
+  signal input openings_plonk_zs_next[NUM_OPENINGS_PLONK_ZS_NEXT()][2];
+  signal input openings_partial_products[NUM_OPENINGS_PARTIAL_PRODUCTS()][2];
+  signal input openings_quotient_polys[NUM_OPENINGS_QUOTIENT_POLYS()][2];
+
+  signal input fri_commit_phase_merkle_caps[NUM_FRI_COMMIT_ROUND()][FRI_COMMIT_MERKLE_CAP_HEIGHT()][4];
+  signal input fri_query_init_constants_sigmas_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_V()];
+  signal input fri_query_init_constants_sigmas_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_P()][4];
+  signal input fri_query_init_wires_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_WIRES_V()];
+  signal input fri_query_init_wires_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_WIRES_P()][4];
+  signal input fri_query_init_zs_partial_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_ZS_PARTIAL_V()];
+  signal input fri_query_init_zs_partial_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_ZS_PARTIAL_P()][4];
+  signal input fri_query_init_quotient_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_QUOTIENT_V()];
+  signal input fri_query_init_quotient_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_QUOTIENT_P()][4];
+  signal input fri_query_step0_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP0_V()][2];
+  signal input fri_query_step0_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP0_P()][4];
+  signal input fri_query_step1_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP1_V()][2];
+  signal input fri_query_step1_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP1_P()][4];
+  signal input fri_final_poly_ext_v[NUM_FRI_FINAL_POLY_EXT_V()][2];
+  signal input fri_pow_witness;
+  signal input public_inputs[NUM_PUBLIC_INPUTS()];
+
+  component canonical_inputs;
+  if (RANGE_CHECK_INPUTS() == 1) {
+    canonical_inputs = CanonicalProofInputs(verifier_key_mode == 0 ? 0 : 1);
+    canonical_inputs.circuit_digest <== circuit_digest;
+    canonical_inputs.sigma_cap <== sigma_cap;
+    canonical_inputs.wires_cap <== wires_cap;
+    canonical_inputs.plonk_zs_partial_products_cap <== plonk_zs_partial_products_cap;
+    canonical_inputs.quotient_polys_cap <== quotient_polys_cap;
+    canonical_inputs.openings_constants <== openings_constants;
+    canonical_inputs.openings_plonk_sigmas <== openings_plonk_sigmas;
+    canonical_inputs.openings_wires <== openings_wires;
+    canonical_inputs.openings_plonk_zs <== openings_plonk_zs;
+    canonical_inputs.openings_plonk_zs_next <== openings_plonk_zs_next;
+    canonical_inputs.openings_partial_products <== openings_partial_products;
+    canonical_inputs.openings_quotient_polys <== openings_quotient_polys;
+    canonical_inputs.fri_commit_phase_merkle_caps <== fri_commit_phase_merkle_caps;
+    canonical_inputs.fri_query_init_constants_sigmas_v <== fri_query_init_constants_sigmas_v;
+    canonical_inputs.fri_query_init_constants_sigmas_p <== fri_query_init_constants_sigmas_p;
+    canonical_inputs.fri_query_init_wires_v <== fri_query_init_wires_v;
+    canonical_inputs.fri_query_init_wires_p <== fri_query_init_wires_p;
+    canonical_inputs.fri_query_init_zs_partial_v <== fri_query_init_zs_partial_v;
+    canonical_inputs.fri_query_init_zs_partial_p <== fri_query_init_zs_partial_p;
+    canonical_inputs.fri_query_init_quotient_v <== fri_query_init_quotient_v;
+    canonical_inputs.fri_query_init_quotient_p <== fri_query_init_quotient_p;
+    canonical_inputs.fri_query_step0_v <== fri_query_step0_v;
+    canonical_inputs.fri_query_step0_p <== fri_query_step0_p;
+    canonical_inputs.fri_query_step1_v <== fri_query_step1_v;
+    canonical_inputs.fri_query_step1_p <== fri_query_step1_p;
+    canonical_inputs.fri_final_poly_ext_v <== fri_final_poly_ext_v;
+    canonical_inputs.fri_pow_witness <== fri_pow_witness;
+    canonical_inputs.public_inputs <== public_inputs;
+  }
+
+  component public_input_hasher = HashPublicInputs(NUM_PUBLIC_INPUTS());
+  public_input_hasher.in <== public_inputs;
+
+  component get_challenges = GetChallengesWithKey();
+
+  get_challenges.circuit_digest <== circuit_digest;
+
+  get_challenges.wires_cap <== wires_cap;
+  get_challenges.plonk_zs_partial_products_cap <== plonk_zs_partial_products_cap;
+  get_challenges.quotient_polys_cap <== quotient_polys_cap;
+
+  get_challenges.openings_constants <== openings_constants;
+  get_challenges.openings_plonk_sigmas <== openings_plonk_sigmas;
+  get_challenges.openings_wires <== openings_wires;
+  get_challenges.openings_plonk_zs <== openings_plonk_zs;
+  get_challenges.openings_plonk_zs_next <== openings_plonk_zs_next;
+  get_challenges.openings_partial_products <== openings_partial_products;
+  get_challenges.openings_quotient_polys <== openings_quotient_polys;
+
+  get_challenges.fri_commit_phase_merkle_caps <== fri_commit_phase_merkle_caps;
+  get_challenges.fri_final_poly_ext_v <== fri_final_poly_ext_v;
+  get_challenges.fri_pow_witness <== fri_pow_witness;
+  get_challenges.public_input_hash <== public_input_hasher.out;
+
+  component eval_vanishing_poly = EvalVanishingPoly();
+
+  eval_vanishing_poly.plonk_betas <== get_challenges.plonk_betas;
+  eval_vanishing_poly.plonk_zeta <== get_challenges.plonk_zeta;
+  eval_vanishing_poly.plonk_gammas <== get_challenges.plonk_gammas;
+  eval_vanishing_poly.openings_constants <== openings_constants;
+  eval_vanishing_poly.openings_wires <== openings_wires;
+  eval_vanishing_poly.openings_plonk_zs <== openings_plonk_zs;
+  eval_vanishing_poly.openings_plonk_sigmas <== openings_plonk_sigmas;
+  eval_vanishing_poly.openings_plonk_zs_next <== openings_plonk_zs_next;
+  eval_vanishing_poly.openings_partial_products <== openings_partial_products;
+  eval_vanishing_poly.public_input_hash <== public_input_hasher.out;
+
+  component check_zeta = CheckZeta();
+
+  check_zeta.openings_quotient_polys <== openings_quotient_polys;
+  check_zeta.plonk_alphas <== get_challenges.plonk_alphas;
+  check_zeta.plonk_zeta <== get_challenges.plonk_zeta;
+  check_zeta.constraint_terms <== eval_vanishing_poly.constraint_terms;
+  check_zeta.vanishing_partial_products_terms <== eval_vanishing_poly.vanishing_partial_products_terms;
+  check_zeta.vanishing_z_1_terms <== eval_vanishing_poly.vanishing_z_1_terms;
+
+  component verify_fri_proof = VerifyFriProofWithKey();
+
+  verify_fri_proof.sigma_cap <== sigma_cap;
+
+  verify_fri_proof.wires_cap <== wires_cap;
+  verify_fri_proof.plonk_zs_partial_products_cap <== plonk_zs_partial_products_cap;
+  verify_fri_proof.quotient_polys_cap <== quotient_polys_cap;
+
+  verify_fri_proof.openings_constants <== openings_constants;
+  verify_fri_proof.openings_plonk_sigmas <== openings_plonk_sigmas;
+  verify_fri_proof.openings_wires <== openings_wires;
+  verify_fri_proof.openings_plonk_zs <== openings_plonk_zs;
+  verify_fri_proof.openings_plonk_zs_next <== openings_plonk_zs_next;
+  verify_fri_proof.openings_partial_products <== openings_partial_products;
+  verify_fri_proof.openings_quotient_polys <== openings_quotient_polys;
+
+  verify_fri_proof.fri_commit_phase_merkle_caps <== fri_commit_phase_merkle_caps;
+  verify_fri_proof.fri_query_init_constants_sigmas_v <== fri_query_init_constants_sigmas_v;
+  verify_fri_proof.fri_query_init_constants_sigmas_p <== fri_query_init_constants_sigmas_p;
+  verify_fri_proof.fri_query_init_wires_v <== fri_query_init_wires_v;
+  verify_fri_proof.fri_query_init_wires_p <== fri_query_init_wires_p;
+  verify_fri_proof.fri_query_init_zs_partial_v <== fri_query_init_zs_partial_v;
+  verify_fri_proof.fri_query_init_zs_partial_p <== fri_query_init_zs_partial_p;
+  verify_fri_proof.fri_query_init_quotient_v <== fri_query_init_quotient_v;
+  verify_fri_proof.fri_query_init_quotient_p <== fri_query_init_quotient_p;
+  verify_fri_proof.fri_query_step0_v <== fri_query_step0_v;
+  verify_fri_proof.fri_query_step0_p <== fri_query_step0_p;
+  verify_fri_proof.fri_query_step1_v <== fri_query_step1_v;
+  verify_fri_proof.fri_query_step1_p <== fri_query_step1_p;
+  verify_fri_proof.fri_final_poly_ext_v <== fri_final_poly_ext_v;
+
+  // Challenges
+  verify_fri_proof.plonk_zeta <== get_challenges.plonk_zeta;
+  verify_fri_proof.fri_alpha <== get_challenges.fri_alpha;
+  verify_fri_proof.fri_betas <== get_challenges.fri_betas;
+  verify_fri_proof.fri_pow_response <== get_challenges.fri_pow_response;
+  verify_fri_proof.fri_query_indices <== get_challenges.fri_query_indices;
+}
+
+
+// VerifyPlonky2ProofWithKey for the verifier key set by the generator.
+template VerifyPlonky2Proof() {
+  signal input wires_cap[NUM_WIRES_CAP()][4];
+  signal input plonk_zs_partial_products_cap[NUM_PLONK_ZS_PARTIAL_PRODUCTS_CAP()][4];
+  signal input quotient_polys_cap[NUM_QUOTIENT_POLYS_CAP()][4];
+
+  signal input openings_constants[NUM_OPENINGS_CONSTANTS()][2];
+  signal input openings_plonk_sigmas[NUM_OPENINGS_PLONK_SIGMAS()][2];
+  signal input openings_wires[NUM_OPENINGS_WIRES()][2];
+  signal input openings_plonk_zs[NUM_OPENINGS_PLONK_ZS()][2];
+  signal input openings_plonk_zs_next[NUM_OPENINGS_PLONK_ZS_NEXT()][2];
+  signal input openings_partial_products[NUM_OPENINGS_PARTIAL_PRODUCTS()][2];
+  signal input openings_quotient_polys[NUM_OPENINGS_QUOTIENT_POLYS()][2];
+
+  signal input fri_commit_phase_merkle_caps[NUM_FRI_COMMIT_ROUND()][FRI_COMMIT_MERKLE_CAP_HEIGHT()][4];
+  signal input fri_query_init_constants_sigmas_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_V()];
+  signal input fri_query_init_constants_sigmas_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_CONSTANTS_SIGMAS_P()][4];
+  signal input fri_query_init_wires_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_WIRES_V()];
+  signal input fri_query_init_wires_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_WIRES_P()][4];
+  signal input fri_query_init_zs_partial_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_ZS_PARTIAL_V()];
+  signal input fri_query_init_zs_partial_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_ZS_PARTIAL_P()][4];
+  signal input fri_query_init_quotient_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_QUOTIENT_V()];
+  signal input fri_query_init_quotient_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_INIT_QUOTIENT_P()][4];
+  signal input fri_query_step0_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP0_V()][2];
+  signal input fri_query_step0_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP0_P()][4];
+  signal input fri_query_step1_v[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP1_V()][2];
+  signal input fri_query_step1_p[NUM_FRI_QUERY_ROUND()][NUM_FRI_QUERY_STEP1_P()][4];
+  signal input fri_final_poly_ext_v[NUM_FRI_FINAL_POLY_EXT_V()][2];
+  signal input fri_pow_witness;
+  signal input public_inputs[NUM_PUBLIC_INPUTS()];
+
+  var cd[4];
+  cd = CIRCUIT_DIGEST();
+  component c = VerifyPlonky2ProofWithKey(0);
+  for (var i = 0; i < 4; i++) {
+    c.circuit_digest[i] <== cd[i];
+  }
+  for (var i = 0; i < NUM_SIGMA_CAPS(); i++) {
+    var cap[4];
+    cap = GET_SIGMA_CAP(i);
+    for (var j = 0; j < 4; j++) {
+      c.sigma_cap[i][j] <== cap[j];
+    }
+  }
+
+  c.wires_cap <== wires_cap;
+  c.plonk_zs_partial_products_cap <== plonk_zs_partial_products_cap;
+  c.quotient_polys_cap <== quotient_polys_cap;
+
+  c.openings_constants <== openings_constants;
+  c.openings_plonk_sigmas <== openings_plonk_sigmas;
+  c.openings_wires <== openings_wires;
+  c.openings_plonk_zs <== openings_plonk_zs;
+  c.openings_plonk_zs_next <== openings_plonk_zs_next;
+  c.openings_partial_products <== openings_partial_products;
+  c.openings_quotient_polys <== openings_quotient_polys;
+
+  c.fri_commit_phase_merkle_caps <== fri_commit_phase_merkle_caps;
+  c.fri_query_init_constants_sigmas_v <== fri_query_init_constants_sigmas_v;
+  c.fri_query_init_constants_sigmas_p <== fri_query_init_constants_sigmas_p;
+  c.fri_query_init_wires_v <== fri_query_init_wires_v;
+  c.fri_query_init_wires_p <== fri_query_init_wires_p;
+  c.fri_query_init_zs_partial_v <== fri_query_init_zs_partial_v;
+  c.fri_query_init_zs_partial_p <== fri_query_init_zs_partial_p;
+  c.fri_query_init_quotient_v <== fri_query_init_quotient_v;
+  c.fri_query_init_quotient_p <== fri_query_init_quotient_p;
+  c.fri_query_step0_v <== fri_query_step0_v;
+  c.fri_query_step0_p <== fri_query_step0_p;
+  c.fri_query_step1_v <== fri_query_step1_v;
+  c.fri_query_step1_p <== fri_query_step1_p;
+  c.fri_final_poly_ext_v <== fri_final_poly_ext_v;
+  c.fri_pow_witness <== fri_pow_witness;
+  c.public_inputs <== public_inputs;
+}
@@ -2,7 +2,10 @@ import { HardhatUserConfig } from "hardhat/config";
 import "@nomicfoundation/hardhat-toolbox";
 
 const config: HardhatUserConfig = {
-  solidity: "0.6.11",
+  // verifier.sol is exported by snarkjs, Plonky2Verifier.sol by solidity.rs.
+  solidity: {
+    compilers: [{ version: "0.6.11" }, { version: "0.8.17" }],
+  },
 };
 
 export default config;
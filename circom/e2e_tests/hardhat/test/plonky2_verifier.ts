@@ -0,0 +1,44 @@
+import {ethers} from "hardhat";
+import {expect} from "chai";
+
+describe("Plonky2Verifier", function () {
+    it("Should return the plonky2 public inputs when proof is correct", async function () {
+        const fs = require("fs");
+        // Generated with the `recursive` fixture and copied here by ../run.sh.
+        if (!fs.existsSync("./contracts/Plonky2Verifier.sol")) {
+            this.skip();
+        }
+
+        const verifierFactory = await ethers.getContractFactory("Verifier");
+        const verifier = await verifierFactory.deploy();
+        await verifier.deployed();
+        const wrapperFactory = await ethers.getContractFactory("Plonky2Verifier");
+        const wrapper = await wrapperFactory.deploy(verifier.address);
+        await wrapper.deployed();
+
+        let text = fs.readFileSync("./test/public.txt").toString();
+        text = text.replace(/\s+/g, '');
+        text = text.replace(/\[+/g, '');
+        text = text.replace(/]+/g, '');
+        text = text.replace(/"+/g, '');
+        const p = text.split(",");
+        let public_inputs = [];
+        for (let i = 0; i < p.length - 8; i++) {
+            public_inputs.push(p[8 + i]);
+        }
+        const a = [p[0], p[1]];
+        const b = [[p[2], p[3]], [p[4], p[5]]];
+        const c = [p[6], p[7]];
+
+        // The plonky2 public inputs are the last public signals.
+        const values = await wrapper.verifyProof(a, b, c, public_inputs);
+        const expected = public_inputs.slice(public_inputs.length - values.length);
+        expect(values.map((x: any) => x.toString())).to.deep.equal(
+            expected.map((x: string) => BigInt(x).toString())
+        );
+
+        let wrong_inputs = [...public_inputs];
+        wrong_inputs[wrong_inputs.length - 1] = "0x0";
+        await expect(wrapper.verifyProof(a, b, c, wrong_inputs)).to.be.reverted;
+    });
+});
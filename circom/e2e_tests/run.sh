@@ -8,6 +8,14 @@ NODE_PATH=node
 SNARKJS_PATH=../../../../snarkjs/cli.js
 NODE_PARAMS="--trace-gc --trace-gc-ignore-scavenger --max-old-space-size=2048000 --initial-old-space-size=2048000 --no-global-gc-scheduling --no-incremental-marking --max-semi-space-size=1024 --initial-heap-size=2048000 --expose-gc"
 
+echo "****GENERATING FIXTURE****"
+# Installs the circuit constants, proof.json and Plonky2Verifier.sol of one proof, so that they
+# all match. See `cargo run --release --bin fixtures -- --help`.
+start=$(date +%s)
+(cd ../.. && cargo run --release --bin fixtures -- --install recursive) || exit 1
+end=$(date +%s)
+echo "DONE ($((end - start))s)"
+
 echo "****COMPILING CIRCUIT****"
 start=$(date +%s)
 circom ${CIRCUIT_PATH} --r1cs --sym --c
@@ -69,4 +77,6 @@ echo "****SOLIDITY VERIFIER TEST****"
 ${NODE_PATH} ${SNARKJS_PATH} zkey export solidityverifier "$CIRCUIT_NAME".zkey verifier.sol
 ${NODE_PATH} ${SNARKJS_PATH} generatecall public.json > ./hardhat/test/public.txt
 cp verifier.sol ./hardhat/contracts
+# Plonky2Verifier.sol of the fixture generated above.
+cp ../test/fixtures/recursive/Plonky2Verifier.sol ./hardhat/contracts
 cd hardhat && npx hardhat test && cd ..
@@ -0,0 +1,4 @@
+pragma circom 2.0.9;
+include "../../circuits/goldilocks.circom";
+
+component main = GlCanonical();
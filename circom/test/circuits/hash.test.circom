@@ -0,0 +1,48 @@
+pragma circom 2.0.9;
+include "../../circuits/keccak.circom";
+include "../../circuits/sha256.circom";
+
+template HashTest() {
+  signal input in;
+  signal output out;
+
+  // Dummy input/output
+  in === 1;
+  out <== 1;
+
+  component k = Keccak_GL(4);
+  k.in[0] <== 8917524657281059100;
+  k.in[1] <== 13029010200779371910;
+  k.in[2] <== 16138660518493481604;
+  k.in[3] <== 17277322750214136960;
+  k.in[4] <== 1441151880423231822;
+  for (var i = 5; i < 8; i++) {
+      k.in[i] <== 0;
+  }
+  for (var i = 0; i < 4; i++) {
+      k.capacity[i] <== 0;
+  }
+  k.out[0] === 16488147459148561440;
+  k.out[1] === 1457290170746362221;
+  k.out[2] === 12651249775548936896;
+  k.out[3] === 11915989345467735221;
+
+  component s = Sha256_GL(4);
+  s.in[0] <== 8917524657281059100;
+  s.in[1] <== 13029010200779371910;
+  s.in[2] <== 16138660518493481604;
+  s.in[3] <== 17277322750214136960;
+  s.in[4] <== 1441151880423231822;
+  for (var i = 5; i < 8; i++) {
+      s.in[i] <== 0;
+  }
+  for (var i = 0; i < 4; i++) {
+      s.capacity[i] <== 0;
+  }
+  s.out[0] === 13778577098166694463;
+  s.out[1] === 7350863156099508384;
+  s.out[2] === 15848949281554442387;
+  s.out[3] === 3039313655834311025;
+}
+
+component main = HashTest();
@@ -21,3 +21,59 @@ describe("Goldilocks Circuit Test", function () {
         await circuit.assertOut(w, {out: 1});
     });
 });
+
+describe("Goldilocks Canonical Circuit Test", function () {
+    let circuit;
+
+    this.timeout(10000000);
+
+    before(async () => {
+        circuit = await wasm_tester(path.join(__dirname, "circuits", "gl_canonical.test.circom"), {});
+    });
+
+    it("Should accept canonical values", async () => {
+        for (const x of ["0", "4294967295", "18446744069414584320"]) {
+            const w = await circuit.calculateWitness({x: x}, true);
+            await circuit.checkConstraints(w);
+        }
+    });
+
+    it("Should reject values that are not canonical", async () => {
+        // Order(), Order() + 5 (aliases 5), 2^64 - 1 and 2^64.
+        for (const x of ["18446744069414584321", "18446744069414584326", "18446744073709551615", "18446744073709551616"]) {
+            let rejected = false;
+            try {
+                await circuit.calculateWitness({x: x}, true);
+            } catch (e) {
+                rejected = true;
+            }
+            if (!rejected) {
+                throw new Error(x + " was accepted");
+            }
+        }
+    });
+});
+
+describe("Goldilocks Reduce Bits Circuit Test", function () {
+    let circuit;
+
+    this.timeout(10000000);
+
+    before(async () => {
+        circuit = await wasm_tester(path.join(__dirname, "circuits", "gl_reduce_bits.test.circom"), {});
+    });
+
+    it("Should output the canonical element of each 64-bit word", async () => {
+        const order = 18446744069414584321n;
+        // 0, 2^32 - 1, Order() - 1, Order(), Order() + 5 and 2^64 - 1.
+        for (const x of [0n, 4294967295n, order - 1n, order, order + 5n, 18446744073709551615n]) {
+            const bits = [];
+            for (let i = 0n; i < 64n; i++) {
+                bits.push(((x >> i) & 1n).toString());
+            }
+            const w = await circuit.calculateWitness({in: bits}, true);
+            await circuit.checkConstraints(w);
+            await circuit.assertOut(w, {out: (x % order).toString()});
+        }
+    });
+});
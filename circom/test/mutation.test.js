@@ -0,0 +1,66 @@
+const fs = require("fs");
+const path = require("path");
+
+const wasm_tester = require("circom_tester").wasm;
+
+// Written by `cargo run --release --bin fixtures -- --install <scenario>` for the installed proof.
+const dir = path.join(__dirname, "data", "mutations");
+const fri = fs.readFileSync(path.join(__dirname, "../circuits", "fri.circom")).toString().split("\n");
+
+// The `expected_failure` label of mutation.rs for the assertion the witness computation stopped
+// at, from the innermost templates of the error and the source line in the first one.
+function failedCheck(error) {
+    const frames = [...error.message.matchAll(/Error in template (\w+)_\d+ line: (\d+)/g)]
+        .map(m => ({template: m[1], line: Number(m[2])}));
+    if (frames.length > 0) {
+        const [first, second] = frames;
+        if (first.template == "CheckZeta") {
+            return "check_zeta";
+        }
+        if (first.template == "LessNBits" && second && second.template == "VerifyFriProofWithKey") {
+            return "fri_pow";
+        }
+        const source = fri[first.line - 1];
+        if (first.template == "VerifyFriProofWithKey" && source.includes("merkle_caps[") && source.includes(".digest[")) {
+            return "merkle_proof";
+        }
+    }
+    return error.message;
+}
+
+describe("Plonky2 Verifier Mutation Test", function () {
+    let circuit;
+
+    this.timeout(100000000);
+
+    before(async function () {
+        // Only there once the fixture is installed.
+        if (!fs.existsSync(path.join(dir, "mutations.json"))) {
+            this.skip();
+        }
+        circuit = await wasm_tester(path.join(__dirname, "../circuits", "plonky2.circom"), {});
+    });
+
+    it("Should reject every mutated proof at its expected check", async () => {
+        const manifest = JSON.parse(fs.readFileSync(path.join(dir, "mutations.json")).toString());
+        if (manifest.length == 0) {
+            throw new Error("no mutations in " + dir);
+        }
+        for (const m of manifest) {
+            const input = JSON.parse(fs.readFileSync(path.join(dir, m.file)).toString());
+            let error = null;
+            try {
+                await circuit.calculateWitness(input, true);
+            } catch (e) {
+                error = e;
+            }
+            if (error == null) {
+                throw new Error(m.mutation + " was accepted");
+            }
+            const check = failedCheck(error);
+            if (check != m.expected_failure) {
+                throw new Error(m.mutation + " failed " + check + ", expected " + m.expected_failure);
+            }
+        }
+    });
+});
@@ -0,0 +1,35 @@
+const path = require("path");
+const proof = require("./data/proof.json");
+
+const wasm_tester = require("circom_tester").wasm;
+
+describe("Plonky2 Verifier Circuit Test", function () {
+    let circuit;
+
+    this.timeout(100000000);
+
+    before(async () => {
+        circuit = await wasm_tester(path.join(__dirname, "../circuits", "plonky2.circom"), {});
+    });
+
+    it("Should accept the proof", async () => {
+        const w = await circuit.calculateWitness(proof, true);
+        await circuit.checkConstraints(w);
+    });
+
+    it("Should reject a proof with a value that is not canonical", async () => {
+        // wires_cap[0][0] + Order() is the same Goldilocks element, which the templates would
+        // accept after reducing it without CanonicalProofInputs.
+        const input = JSON.parse(JSON.stringify(proof));
+        input.wires_cap[0][0] = (BigInt(input.wires_cap[0][0]) + 18446744069414584321n).toString();
+        let rejected = false;
+        try {
+            await circuit.calculateWitness(input, true);
+        } catch (e) {
+            rejected = true;
+        }
+        if (!rejected) {
+            throw new Error("wires_cap[0][0] + Order() was accepted");
+        }
+    });
+});
@@ -0,0 +1,62 @@
+//! Checks a witness against a Circom constraint system and prints every unsatisfied constraint
+//! with the names of its signals.
+//!
+//! Usage: `check_witness <circuit.r1cs> <circuit.sym> <witness.wtns> [max_reported]`
+
+use std::fs::File;
+use std::io::BufReader;
+use std::process::exit;
+
+use anyhow::{bail, ensure, Context, Result};
+use plonky2_circom_verifier::snarkjs::{R1cs, Symbols, Wtns};
+
+fn main() -> Result<()> {
+    let args = std::env::args().collect::<Vec<_>>();
+    if args.len() != 4 && args.len() != 5 {
+        bail!(
+            "usage: {} <circuit.r1cs> <circuit.sym> <witness.wtns> [max_reported]",
+            args[0]
+        );
+    }
+    let max_reported = match args.get(4) {
+        Some(n) => n.parse().context("invalid max_reported")?,
+        None => 10,
+    };
+
+    let open = |path: &String| -> Result<BufReader<File>> {
+        Ok(BufReader::new(
+            File::open(path).with_context(|| format!("cannot open {}", path))?,
+        ))
+    };
+    let r1cs = R1cs::read(open(&args[1])?).context("cannot read the constraints")?;
+    let symbols = Symbols::read(open(&args[2])?).context("cannot read the symbols")?;
+    let wtns = Wtns::read(open(&args[3])?).context("cannot read the witness")?;
+    ensure!(
+        wtns.prime == r1cs.prime,
+        "witness and constraints use different fields"
+    );
+    ensure!(
+        wtns.witness.len() == r1cs.num_wires as usize,
+        "witness has {} values, expected {}",
+        wtns.witness.len(),
+        r1cs.num_wires
+    );
+
+    let mut num_unsatisfied = 0;
+    for i in r1cs.unsatisfied(&wtns.witness) {
+        if num_unsatisfied < max_reported {
+            print!("{}", r1cs.describe(i, &wtns.witness, &symbols));
+        }
+        num_unsatisfied += 1;
+    }
+    if num_unsatisfied == 0 {
+        println!("all {} constraints are satisfied", r1cs.constraints.len());
+        return Ok(());
+    }
+    println!(
+        "{} of {} constraints are not satisfied",
+        num_unsatisfied,
+        r1cs.constraints.len()
+    );
+    exit(1);
+}
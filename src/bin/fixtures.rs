@@ -0,0 +1,90 @@
+//! Generates the Circom verifier fixtures of each scenario into `circom/test/fixtures/<scenario>`.
+//!
+//! Usage: `fixtures [--check] [--install <scenario>] [scenario...]`
+//!
+//! Without scenarios, all of them are generated. `--check` fails if the committed fixtures, or the
+//! installed copy of the `recursive` scenario, are out of date instead of writing them.
+//! `--install` also copies one scenario to `circom/circuits` and `circom/test/data`, where the
+//! Circom tests read it.
+
+use std::path::Path;
+use std::process::exit;
+
+use anyhow::{bail, Context, Result};
+use plonky2_circom_verifier::fixtures::{Scenario, FIXTURES_DIR, INSTALLED_SCENARIO};
+
+fn main() -> Result<()> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let usage = format!(
+        "usage: {} [--check] [--install <scenario>] [scenario...]\nscenarios: {}",
+        args[0],
+        Scenario::ALL.map(|s| s.name()).join(", ")
+    );
+
+    let mut check = false;
+    let mut install = None;
+    let mut scenarios = vec![];
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--install" => {
+                let name = rest.next().context(usage.clone())?;
+                install = Some(Scenario::from_name(name)?);
+            }
+            "--help" | "-h" => {
+                println!("{}", usage);
+                return Ok(());
+            }
+            name if name.starts_with('-') => bail!("unknown option {}\n{}", name, usage),
+            name => scenarios.push(Scenario::from_name(name)?),
+        }
+    }
+    if check && install.is_some() {
+        bail!("--check and --install cannot be combined");
+    }
+    if scenarios.is_empty() {
+        scenarios = Scenario::ALL.to_vec();
+    }
+    if let Some(scenario) = install {
+        if !scenarios.contains(&scenario) {
+            scenarios.push(scenario);
+        }
+    }
+
+    let mut out_of_date = 0;
+    for scenario in scenarios {
+        let dir = Path::new(FIXTURES_DIR).join(scenario.name());
+        let fixture = scenario
+            .generate()
+            .with_context(|| format!("cannot generate {}", scenario.name()))?;
+        if check {
+            let mut result = fixture.check(&dir);
+            if scenario == INSTALLED_SCENARIO {
+                result = result.and_then(|()| fixture.check_installed(Path::new("./circom")));
+            }
+            match result {
+                Ok(()) => println!("{}: up to date", scenario.name()),
+                Err(e) => {
+                    println!("{}: {:#}", scenario.name(), e);
+                    out_of_date += 1;
+                }
+            }
+            continue;
+        }
+        fixture.write(&dir)?;
+        println!("{}: written to {}", scenario.name(), dir.display());
+        if install == Some(scenario) {
+            fixture.install(Path::new("./circom"))?;
+            println!("{}: installed to ./circom", scenario.name());
+        }
+    }
+    if out_of_date > 0 {
+        println!(
+            "{} scenario(s) out of date, regenerate them with `cargo run --release --bin fixtures`",
+            out_of_date
+        );
+        exit(1);
+    }
+    Ok(())
+}
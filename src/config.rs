@@ -1,104 +1,77 @@
+use std::marker::PhantomData;
+
 use plonky2::field::extension::quadratic::QuadraticExtension;
-use plonky2::field::extension::Extendable;
 use plonky2::field::goldilocks_field::GoldilocksField;
 
-use plonky2::hash::hash_types::{HashOut, HashOutTarget, RichField};
-use plonky2::hash::hashing::{compress, hash_n_to_hash_no_pad, PlonkyPermutation, SPONGE_WIDTH};
+use keccak_hash::keccak;
+use plonky2::hash::hash_types::{HashOut, RichField};
+use plonky2::hash::hashing::{
+    compress, hash_n_to_hash_no_pad, PlonkyPermutation, SPONGE_RATE, SPONGE_WIDTH,
+};
+use plonky2::hash::merkle_tree::{MerkleCap, MerkleTree};
 use plonky2::hash::poseidon::PoseidonHash;
-use plonky2::iop::target::{BoolTarget, Target};
-use plonky2::plonk::circuit_builder::CircuitBuilder;
-use plonky2::plonk::config::{AlgebraicHasher, GenericConfig, Hasher};
-use poseidon_permutation::bindings::permute;
+use plonky2::plonk::config::{GenericConfig, Hasher};
+use plonky2_util::log2_strict;
+use sha2::{Digest, Sha256};
+
+use crate::poseidon_bn128;
 
+/// Poseidon over BN254 on a Goldilocks sponge state, see `poseidon_bn128::permute`.
 pub struct PoseidonBN128Permutation;
 impl<F: RichField> PlonkyPermutation<F> for PoseidonBN128Permutation {
     fn permute(input: [F; SPONGE_WIDTH]) -> [F; SPONGE_WIDTH] {
         assert_eq!(SPONGE_WIDTH, 12);
-        unsafe {
-            let h = permute(
-                input[0].to_canonical_u64(),
-                input[1].to_canonical_u64(),
-                input[2].to_canonical_u64(),
-                input[3].to_canonical_u64(),
-                input[4].to_canonical_u64(),
-                input[5].to_canonical_u64(),
-                input[6].to_canonical_u64(),
-                input[7].to_canonical_u64(),
-                input[8].to_canonical_u64(),
-                input[9].to_canonical_u64(),
-                input[10].to_canonical_u64(),
-                input[11].to_canonical_u64(),
-            );
+        assert_eq!(F::ORDER, poseidon_bn128::GOLDILOCKS_ORDER);
+        let input = input.map(|x| x.to_canonical_u64());
+        poseidon_bn128::permute(input).map(F::from_canonical_u64)
+    }
+}
 
-            [
-                F::from_canonical_u64(if h.r0 >= F::ORDER {
-                    h.r0 - F::ORDER
-                } else {
-                    h.r0
-                }),
-                F::from_canonical_u64(if h.r1 >= F::ORDER {
-                    h.r1 - F::ORDER
-                } else {
-                    h.r1
-                }),
-                F::from_canonical_u64(if h.r2 >= F::ORDER {
-                    h.r2 - F::ORDER
-                } else {
-                    h.r2
-                }),
-                F::from_canonical_u64(if h.r3 >= F::ORDER {
-                    h.r3 - F::ORDER
-                } else {
-                    h.r3
-                }),
-                F::from_canonical_u64(if h.r4 >= F::ORDER {
-                    h.r4 - F::ORDER
-                } else {
-                    h.r4
-                }),
-                F::from_canonical_u64(if h.r5 >= F::ORDER {
-                    h.r5 - F::ORDER
-                } else {
-                    h.r5
-                }),
-                F::from_canonical_u64(if h.r6 >= F::ORDER {
-                    h.r6 - F::ORDER
-                } else {
-                    h.r6
-                }),
-                F::from_canonical_u64(if h.r7 >= F::ORDER {
-                    h.r7 - F::ORDER
-                } else {
-                    h.r7
-                }),
-                F::from_canonical_u64(if h.r8 >= F::ORDER {
-                    h.r8 - F::ORDER
-                } else {
-                    h.r8
-                }),
-                F::from_canonical_u64(if h.r9 >= F::ORDER {
-                    h.r9 - F::ORDER
-                } else {
-                    h.r9
-                }),
-                F::from_canonical_u64(if h.r10 >= F::ORDER {
-                    h.r10 - F::ORDER
-                } else {
-                    h.r10
-                }),
-                F::from_canonical_u64(if h.r11 >= F::ORDER {
-                    h.r11 - F::ORDER
-                } else {
-                    h.r11
-                }),
-            ]
+impl PoseidonBN128Permutation {
+    /// `permute` on every state, faster than one at a time.
+    pub fn permute_many<F: RichField>(states: &mut [[F; SPONGE_WIDTH]]) {
+        assert_eq!(F::ORDER, poseidon_bn128::GOLDILOCKS_ORDER);
+        let mut inputs = states
+            .iter()
+            .map(|state| state.map(|x| x.to_canonical_u64()))
+            .collect::<Vec<_>>();
+        poseidon_bn128::permute_many(&mut inputs);
+        for (state, output) in states.iter_mut().zip(inputs) {
+            *state = output.map(F::from_canonical_u64);
         }
     }
 }
 
+/// Poseidon over BN254, cheap to verify in Circom.
+///
+/// There is no in-circuit gadget for it, so it deliberately does not implement
+/// `AlgebraicHasher` and proofs using it cannot be verified recursively in plonky2:
+///
+/// ```compile_fail
+/// use plonky2::plonk::circuit_builder::CircuitBuilder;
+/// use plonky2::plonk::circuit_data::{CircuitConfig, VerifierCircuitTarget};
+/// use plonky2::plonk::config::GenericConfig;
+/// use plonky2_circom_verifier::config::PoseidonBN128GoldilocksConfig;
+///
+/// type C = PoseidonBN128GoldilocksConfig;
+/// type F = <C as GenericConfig<2>>::F;
+/// let config = CircuitConfig::standard_recursion_config();
+/// let inner_cd = CircuitBuilder::<F, 2>::new(config.clone()).build::<C>().common;
+///
+/// let mut builder = CircuitBuilder::<F, 2>::new(config);
+/// let pt = builder.add_virtual_proof_with_pis::<C>(&inner_cd);
+/// let inner_data = VerifierCircuitTarget {
+///     constants_sigmas_cap: builder.add_virtual_cap(inner_cd.config.fri_config.cap_height),
+///     circuit_digest: builder.add_virtual_hash(),
+/// };
+/// builder.verify_proof::<C>(&pt, &inner_data, &inner_cd);
+/// ```
+///
+/// Public inputs are hashed with `PI`, see `CircomPublicInputHasher`. The same goes for
+/// `Keccak256Hash` and `Sha256Hash`.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub struct PoseidonBN128Hash;
-impl<F: RichField> Hasher<F> for PoseidonBN128Hash {
+pub struct PoseidonBN128Hash<PI = PoseidonHash>(PhantomData<PI>);
+impl<F: RichField, PI: CircomPublicInputHasher<F>> Hasher<F> for PoseidonBN128Hash<PI> {
     const HASH_SIZE: usize = 4 * 8;
     type Hash = HashOut<F>;
     type Permutation = PoseidonBN128Permutation;
@@ -108,7 +81,7 @@ impl<F: RichField> Hasher<F> for PoseidonBN128Hash {
     }
 
     fn hash_public_inputs(input: &[F]) -> Self::Hash {
-        PoseidonHash::hash_no_pad(input)
+        PI::hash_no_pad(input)
     }
 
     fn two_to_one(left: Self::Hash, right: Self::Hash) -> Self::Hash {
@@ -116,30 +89,129 @@ impl<F: RichField> Hasher<F> for PoseidonBN128Hash {
     }
 }
 
-// TODO: this is a work around. Still use Goldilocks based Poseidon for algebraic PoseidonBN128Hash.
-impl<F: RichField> AlgebraicHasher<F> for PoseidonBN128Hash {
-    fn permute_swapped<const D: usize>(
-        inputs: [Target; SPONGE_WIDTH],
-        swap: BoolTarget,
-        builder: &mut CircuitBuilder<F, D>,
-    ) -> [Target; SPONGE_WIDTH]
+impl<PI> PoseidonBN128Hash<PI> {
+    /// Builds the same tree as `MerkleTree::new`, but hashes each layer with one call to
+    /// `PoseidonBN128Permutation::permute_many`.
+    ///
+    /// The plonky2 prover still builds its own trees node by node through `two_to_one`.
+    pub fn merkle_tree<F: RichField>(leaves: Vec<Vec<F>>, cap_height: usize) -> MerkleTree<F, Self>
     where
-        F: RichField + Extendable<D>,
+        PI: CircomPublicInputHasher<F>,
     {
-        PoseidonHash::permute_swapped(inputs, swap, builder)
+        let log2_leaves = log2_strict(leaves.len());
+        assert!(
+            cap_height <= log2_leaves,
+            "cap_height={cap_height} should be at most log2(leaves.len())={log2_leaves}"
+        );
+
+        let mut layers = vec![Self::hash_leaves(&leaves)];
+        while layers.last().unwrap().len() > 1 << cap_height {
+            let mut states = layers
+                .last()
+                .unwrap()
+                .chunks_exact(2)
+                .map(|pair| {
+                    let mut state = [F::ZERO; SPONGE_WIDTH];
+                    state[..4].copy_from_slice(&pair[0].elements);
+                    state[4..8].copy_from_slice(&pair[1].elements);
+                    state
+                })
+                .collect::<Vec<_>>();
+            PoseidonBN128Permutation::permute_many(&mut states);
+            layers.push(states.iter().map(digest_of_state).collect());
+        }
+
+        let zero = HashOut {
+            elements: [F::ZERO; 4],
+        };
+        let mut digests = vec![zero; 2 * (leaves.len() - (1 << cap_height))];
+        let height = log2_leaves - cap_height;
+        if height > 0 {
+            let subtree_len = digests.len() >> cap_height;
+            for (i, subtree) in digests.chunks_exact_mut(subtree_len).enumerate() {
+                fill_subtree(subtree, &layers, height, i);
+            }
+        }
+        let cap = MerkleCap(layers.pop().unwrap());
+        MerkleTree {
+            leaves,
+            digests,
+            cap,
+        }
     }
-    fn public_inputs_hash<const D: usize>(
-        inputs: Vec<Target>,
-        builder: &mut CircuitBuilder<F, D>,
-    ) -> HashOutTarget
+
+    /// `hash_or_noop` on every leaf, absorbing the leaves that need the sponge in lockstep.
+    fn hash_leaves<F: RichField>(leaves: &[Vec<F>]) -> Vec<HashOut<F>>
     where
-        F: RichField + Extendable<D>,
+        PI: CircomPublicInputHasher<F>,
     {
-        PoseidonHash::public_inputs_hash(inputs, builder)
+        let sponged = (0..leaves.len())
+            .filter(|&i| leaves[i].len() * 8 > <Self as Hasher<F>>::HASH_SIZE)
+            .collect::<Vec<_>>();
+        let mut states = vec![[F::ZERO; SPONGE_WIDTH]; sponged.len()];
+        for offset in (0..).step_by(SPONGE_RATE) {
+            let active = (0..sponged.len())
+                .filter(|&k| leaves[sponged[k]].len() > offset)
+                .collect::<Vec<_>>();
+            if active.is_empty() {
+                break;
+            }
+            let mut batch = active
+                .iter()
+                .map(|&k| {
+                    let leaf = &leaves[sponged[k]];
+                    let chunk = &leaf[offset..leaf.len().min(offset + SPONGE_RATE)];
+                    let mut state = states[k];
+                    state[..chunk.len()].copy_from_slice(chunk);
+                    state
+                })
+                .collect::<Vec<_>>();
+            PoseidonBN128Permutation::permute_many(&mut batch);
+            for (&k, state) in active.iter().zip(batch) {
+                states[k] = state;
+            }
+        }
+
+        let mut hashes = leaves
+            .iter()
+            .map(|leaf| <Self as Hasher<F>>::hash_or_noop(leaf))
+            .collect::<Vec<_>>();
+        for (&i, state) in sponged.iter().zip(&states) {
+            hashes[i] = digest_of_state(state);
+        }
+        hashes
     }
 }
 
-/// Configuration using Poseidon over the Goldilocks field.
+fn digest_of_state<F: RichField>(state: &[F; SPONGE_WIDTH]) -> HashOut<F> {
+    HashOut {
+        elements: state[..4].try_into().unwrap(),
+    }
+}
+
+/// Writes the digests below the node `index` of `layers[height]` in the order of
+/// `MerkleTree::digests`: the left subtree followed by its root, then the root of the right
+/// subtree followed by that subtree.
+fn fill_subtree<F: RichField>(
+    digests: &mut [HashOut<F>],
+    layers: &[Vec<HashOut<F>>],
+    height: usize,
+    index: usize,
+) {
+    if height == 0 {
+        return;
+    }
+    let (left, right) = digests.split_at_mut(digests.len() / 2);
+    let (left_digest, left) = left.split_last_mut().unwrap();
+    let (right_digest, right) = right.split_first_mut().unwrap();
+    *left_digest = layers[height - 1][2 * index];
+    *right_digest = layers[height - 1][2 * index + 1];
+    fill_subtree(left, layers, height - 1, 2 * index);
+    fill_subtree(right, layers, height - 1, 2 * index + 1);
+}
+
+/// Configuration using Poseidon over BN254 for commitments and Fiat-Shamir, as the final layer
+/// verified in Circom. Public inputs are still hashed with Goldilocks Poseidon.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct PoseidonBN128GoldilocksConfig;
 
@@ -147,16 +219,224 @@ impl GenericConfig<2> for PoseidonBN128GoldilocksConfig {
     type F = GoldilocksField;
     type FE = QuadraticExtension<Self::F>;
     type Hasher = PoseidonBN128Hash;
-    type InnerHasher = PoseidonBN128Hash;
+    type InnerHasher = PoseidonHash;
+}
+
+/// Permutes a Goldilocks sponge state with a 256-bit digest, like `Keccak_GL` and `Sha256_GL`
+/// in Circom.
+///
+/// The state is serialized as little-endian `u64`s. Outputs `4 * k..4 * k + 4` are the
+/// little-endian `u64`s of the digest of the state followed by the byte `k`, each reduced once
+/// modulo the field order.
+fn digest_permute<F: RichField>(
+    input: [F; SPONGE_WIDTH],
+    digest: impl Fn(&[u8]) -> [u8; 32],
+) -> [F; SPONGE_WIDTH] {
+    let mut bytes = input
+        .iter()
+        .flat_map(|x| x.to_canonical_u64().to_le_bytes())
+        .collect::<Vec<_>>();
+    bytes.push(0);
+
+    let mut output = [F::ZERO; SPONGE_WIDTH];
+    for (k, chunk) in output.chunks_mut(4).enumerate() {
+        *bytes.last_mut().unwrap() = k as u8;
+        let hash = digest(&bytes);
+        for (x, word) in chunk.iter_mut().zip(hash.chunks_exact(8)) {
+            *x = reduce_limb(u64::from_le_bytes(word.try_into().unwrap()));
+        }
+    }
+    output
+}
+
+fn reduce_limb<F: RichField>(x: u64) -> F {
+    F::from_canonical_u64(if x >= F::ORDER { x - F::ORDER } else { x })
+}
+
+pub struct Keccak256Permutation;
+impl<F: RichField> PlonkyPermutation<F> for Keccak256Permutation {
+    fn permute(input: [F; SPONGE_WIDTH]) -> [F; SPONGE_WIDTH] {
+        digest_permute(input, |bytes| keccak(bytes).0)
+    }
+}
+
+/// Sponge over `Keccak256Permutation`, with the same 4 element digests as `PoseidonBN128Hash`.
+///
+/// Each permutation costs two orders of magnitude more Circom constraints than Poseidon, but the
+/// Merkle proofs can be re-checked cheaply on EVM. The same goes for `Sha256Hash`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Keccak256Hash<PI = PoseidonHash>(PhantomData<PI>);
+impl<F: RichField, PI: CircomPublicInputHasher<F>> Hasher<F> for Keccak256Hash<PI> {
+    const HASH_SIZE: usize = 4 * 8;
+    type Hash = HashOut<F>;
+    type Permutation = Keccak256Permutation;
+
+    fn hash_no_pad(input: &[F]) -> Self::Hash {
+        hash_n_to_hash_no_pad::<F, Self::Permutation>(input)
+    }
+
+    fn hash_public_inputs(input: &[F]) -> Self::Hash {
+        PI::hash_no_pad(input)
+    }
+
+    fn two_to_one(left: Self::Hash, right: Self::Hash) -> Self::Hash {
+        compress::<F, Self::Permutation>(left, right)
+    }
+}
+
+/// Configuration using `Keccak256Hash`, for a final layer whose Merkle proofs are re-checked on
+/// EVM.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct KeccakGoldilocksConfig;
+
+impl GenericConfig<2> for KeccakGoldilocksConfig {
+    type F = GoldilocksField;
+    type FE = QuadraticExtension<Self::F>;
+    type Hasher = Keccak256Hash;
+    type InnerHasher = PoseidonHash;
+}
+
+pub struct Sha256Permutation;
+impl<F: RichField> PlonkyPermutation<F> for Sha256Permutation {
+    fn permute(input: [F; SPONGE_WIDTH]) -> [F; SPONGE_WIDTH] {
+        digest_permute(input, |bytes| Sha256::digest(bytes).into())
+    }
+}
+
+/// Sponge over `Sha256Permutation`, with the same 4 element digests as `PoseidonBN128Hash`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Sha256Hash<PI = PoseidonHash>(PhantomData<PI>);
+impl<F: RichField, PI: CircomPublicInputHasher<F>> Hasher<F> for Sha256Hash<PI> {
+    const HASH_SIZE: usize = 4 * 8;
+    type Hash = HashOut<F>;
+    type Permutation = Sha256Permutation;
+
+    fn hash_no_pad(input: &[F]) -> Self::Hash {
+        hash_n_to_hash_no_pad::<F, Self::Permutation>(input)
+    }
+
+    fn hash_public_inputs(input: &[F]) -> Self::Hash {
+        PI::hash_no_pad(input)
+    }
+
+    fn two_to_one(left: Self::Hash, right: Self::Hash) -> Self::Hash {
+        compress::<F, Self::Permutation>(left, right)
+    }
+}
+
+/// Configuration using `Sha256Hash`, for a final layer whose Merkle proofs are re-checked on
+/// EVM.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Sha256GoldilocksConfig;
+
+impl GenericConfig<2> for Sha256GoldilocksConfig {
+    type F = GoldilocksField;
+    type FE = QuadraticExtension<Self::F>;
+    type Hasher = Sha256Hash;
+    type InnerHasher = PoseidonHash;
+}
+
+/// A hasher the Circom verifier implements, selected in `circom/circuits/hash.circom` by
+/// `HASHER()`. Circom verifiers can only be generated for configs using one:
+///
+/// ```compile_fail
+/// use plonky2::plonk::circuit_data::{CommonCircuitData, VerifierOnlyCircuitData};
+/// use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+/// use plonky2_circom_verifier::verifier::{generate_circom_verifier, VerifierConfig};
+///
+/// type C = PoseidonGoldilocksConfig;
+/// type F = <C as GenericConfig<2>>::F;
+///
+/// fn generate(
+///     conf: &VerifierConfig,
+///     common: &CommonCircuitData<F, 2>,
+///     verifier_only: &VerifierOnlyCircuitData<C, 2>,
+/// ) {
+///     generate_circom_verifier(conf, common, verifier_only).unwrap();
+/// }
+/// ```
+pub trait CircomHasher<F: RichField>: Hasher<F, Hash = HashOut<F>> {
+    const CIRCOM_HASHER: CircomHasherKind;
+
+    /// Hasher of `Hasher::hash_public_inputs`.
+    type PublicInputHasher: CircomPublicInputHasher<F>;
+
+    /// Value of `PUBLIC_INPUT_HASHER()` in the generated constants.
+    const PUBLIC_INPUT_HASHER: CircomPublicInputHasherKind =
+        <Self::PublicInputHasher as CircomPublicInputHasher<F>>::CIRCOM_PUBLIC_INPUT_HASHER;
+}
+
+/// The hashers of `circom/circuits/hash.circom`. The discriminant is the value of `HASHER()` in
+/// the generated constants.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircomHasherKind {
+    PoseidonBN128 = 0,
+    Keccak256 = 1,
+    Sha256 = 2,
+}
+
+impl<F: RichField, PI: CircomPublicInputHasher<F>> CircomHasher<F> for PoseidonBN128Hash<PI> {
+    const CIRCOM_HASHER: CircomHasherKind = CircomHasherKind::PoseidonBN128;
+    type PublicInputHasher = PI;
+}
+
+impl<F: RichField, PI: CircomPublicInputHasher<F>> CircomHasher<F> for Keccak256Hash<PI> {
+    const CIRCOM_HASHER: CircomHasherKind = CircomHasherKind::Keccak256;
+    type PublicInputHasher = PI;
+}
+
+impl<F: RichField, PI: CircomPublicInputHasher<F>> CircomHasher<F> for Sha256Hash<PI> {
+    const CIRCOM_HASHER: CircomHasherKind = CircomHasherKind::Sha256;
+    type PublicInputHasher = PI;
+}
+
+/// A hasher of the public inputs the Circom verifier implements, selected in
+/// `circom/circuits/hash.circom` by `PUBLIC_INPUT_HASHER()`.
+///
+/// Plonky2 also hashes the public inputs inside the circuit with the config's `InnerHasher`, and
+/// the proof only verifies if both hashes agree. `wrap_for_circom` therefore requires it to be
+/// the `InnerHasher`, which rules out the hashers without an in-circuit gadget.
+pub trait CircomPublicInputHasher<F: RichField>: Hasher<F, Hash = HashOut<F>> {
+    const CIRCOM_PUBLIC_INPUT_HASHER: CircomPublicInputHasherKind;
+}
+
+/// The public-input hashers of `circom/circuits/hash.circom`. The discriminant is the value of
+/// `PUBLIC_INPUT_HASHER()` in the generated constants.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircomPublicInputHasherKind {
+    PoseidonGoldilocks = 0,
+    PoseidonBN128 = 1,
+    Keccak256 = 2,
+}
+
+impl<F: RichField> CircomPublicInputHasher<F> for PoseidonHash {
+    const CIRCOM_PUBLIC_INPUT_HASHER: CircomPublicInputHasherKind =
+        CircomPublicInputHasherKind::PoseidonGoldilocks;
+}
+
+impl<F: RichField, PI: CircomPublicInputHasher<F>> CircomPublicInputHasher<F>
+    for PoseidonBN128Hash<PI>
+{
+    const CIRCOM_PUBLIC_INPUT_HASHER: CircomPublicInputHasherKind =
+        CircomPublicInputHasherKind::PoseidonBN128;
+}
+
+impl<F: RichField, PI: CircomPublicInputHasher<F>> CircomPublicInputHasher<F>
+    for Keccak256Hash<PI>
+{
+    const CIRCOM_PUBLIC_INPUT_HASHER: CircomPublicInputHasherKind =
+        CircomPublicInputHasherKind::Keccak256;
 }
 
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
+    use plonky2::field::goldilocks_field::GoldilocksField;
     use plonky2::field::types::Field;
+    use plonky2::hash::merkle_tree::MerkleTree;
     use plonky2::plonk::config::{GenericConfig, Hasher, PoseidonGoldilocksConfig};
 
-    use crate::config::PoseidonBN128Hash;
+    use crate::config::{Keccak256Hash, PoseidonBN128Hash, Sha256Hash};
 
     #[test]
     fn test_poseidon_bn128() -> Result<()> {
@@ -170,7 +450,7 @@ mod tests {
         v.push(F::from_canonical_u64(16138660518493481604u64));
         v.push(F::from_canonical_u64(17277322750214136960u64));
         v.push(F::from_canonical_u64(1441151880423231822u64));
-        let h = PoseidonBN128Hash::hash_no_pad(&v);
+        let h = <PoseidonBN128Hash>::hash_no_pad(&v);
         assert_eq!(h.elements[0].0, 16736853722845225729u64);
         assert_eq!(h.elements[1].0, 1446699130810517790u64);
         assert_eq!(h.elements[2].0, 15445626857806971868u64);
@@ -178,4 +458,83 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_digest_hashes() {
+        type F = GoldilocksField;
+
+        let short = [
+            8917524657281059100u64,
+            13029010200779371910,
+            16138660518493481604,
+            17277322750214136960,
+            1441151880423231822,
+        ]
+        .map(F::from_canonical_u64);
+        // Absorbed with three permutations.
+        let long = (1..=20).map(F::from_canonical_u64).collect::<Vec<_>>();
+
+        let h = <Keccak256Hash>::hash_no_pad(&short);
+        assert_eq!(
+            h.elements.map(|x| x.0),
+            [
+                16488147459148561440,
+                1457290170746362221,
+                12651249775548936896,
+                11915989345467735221
+            ]
+        );
+        let h = <Keccak256Hash>::hash_no_pad(&long);
+        assert_eq!(
+            h.elements.map(|x| x.0),
+            [
+                9854079237739678922,
+                258259599704184552,
+                13196337671178633497,
+                17096846713975809934
+            ]
+        );
+
+        let h = <Sha256Hash>::hash_no_pad(&short);
+        assert_eq!(
+            h.elements.map(|x| x.0),
+            [
+                13778577098166694463,
+                7350863156099508384,
+                15848949281554442387,
+                3039313655834311025
+            ]
+        );
+        let h = <Sha256Hash>::hash_no_pad(&long);
+        assert_eq!(
+            h.elements.map(|x| x.0),
+            [
+                7514034296289003488,
+                14429133831766667568,
+                10053969127604282099,
+                6062871640323192345
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merkle_tree() {
+        type F = GoldilocksField;
+
+        // Leaves short enough to be used as digests and leaves absorbed over several rounds.
+        let leaves = (0..16u64)
+            .map(|i| {
+                let len = [3, 8, 20, 4][i as usize % 4];
+                (0..len)
+                    .map(|j| F::from_canonical_u64(i * 1000 + j))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for cap_height in 0..=4 {
+            let expected = MerkleTree::<F, PoseidonBN128Hash>::new(leaves.clone(), cap_height);
+            let tree = <PoseidonBN128Hash>::merkle_tree(leaves.clone(), cap_height);
+            assert_eq!(tree.digests, expected.digests);
+            assert_eq!(tree.cap, expected.cap);
+        }
+    }
 }
@@ -0,0 +1,531 @@
+use std::fmt;
+
+use crate::config::{CircomHasherKind, CircomPublicInputHasherKind};
+use crate::verifier::{CircomVerifierKey, CircomVerifierOptions, VerifierConfig};
+
+/// Proof bytes and Circom non-linear constraints attributed to one part of the proof.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SectionCost {
+    pub name: String,
+    pub proof_bytes: usize,
+    pub constraints: usize,
+}
+
+/// Per-section estimate of the proof size and of the size of the generated Circom verifier.
+///
+/// Constraint counts are derived from the templates in `circom/circuits` and count non-linear
+/// constraints only, which is what `circom --O2` reports. The plonk vanishing polynomial and the
+/// gate constraints depend on the gate set of the circuit and are not part of the estimate.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CostReport {
+    pub sections: Vec<SectionCost>,
+}
+
+impl CostReport {
+    pub fn total_proof_bytes(&self) -> usize {
+        self.sections.iter().map(|s| s.proof_bytes).sum()
+    }
+
+    pub fn total_constraints(&self) -> usize {
+        self.sections.iter().map(|s| s.constraints).sum()
+    }
+
+    fn push(&mut self, name: &str, proof_bytes: usize, constraints: usize) {
+        self.sections.push(SectionCost {
+            name: name.to_owned(),
+            proof_bytes,
+            constraints,
+        });
+    }
+}
+
+impl fmt::Display for CostReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<24} {:>12} {:>14}",
+            "section", "proof bytes", "constraints"
+        )?;
+        for s in &self.sections {
+            writeln!(
+                f,
+                "{:<24} {:>12} {:>14}",
+                s.name, s.proof_bytes, s.constraints
+            )?;
+        }
+        write!(
+            f,
+            "{:<24} {:>12} {:>14}",
+            "total",
+            self.total_proof_bytes(),
+            self.total_constraints()
+        )
+    }
+}
+
+// Non-linear constraint counts of the Circom templates, named after the templates.
+
+fn less_n_bits(n: usize) -> usize {
+    n
+}
+
+fn gl_reduce(n: usize) -> usize {
+    less_n_bits(n) + less_n_bits(64)
+}
+
+fn gl_canonical() -> usize {
+    64 + 1 + 31 + 1
+}
+
+fn gl_reduce_bits() -> usize {
+    31 + 3
+}
+
+fn gl_add() -> usize {
+    gl_reduce(1)
+}
+
+fn gl_sub() -> usize {
+    gl_reduce(1)
+}
+
+fn gl_mul() -> usize {
+    1 + gl_reduce(64)
+}
+
+fn gl_inv() -> usize {
+    gl_mul()
+}
+
+fn gl_exp() -> usize {
+    64 * (1 + 2 * gl_mul() + 1)
+}
+
+fn gl_exp_power_of_2(n: usize) -> usize {
+    n * gl_mul()
+}
+
+fn gl_ext_add() -> usize {
+    2 * gl_add()
+}
+
+fn gl_ext_sub() -> usize {
+    2 * gl_add()
+}
+
+fn gl_ext_mul() -> usize {
+    5 * gl_mul() + 2 * gl_add()
+}
+
+fn gl_ext_square() -> usize {
+    5 * gl_mul() + gl_add()
+}
+
+fn gl_ext_div() -> usize {
+    2 * gl_ext_mul() + 3 * gl_mul() + gl_inv()
+}
+
+fn gl_ext_exp() -> usize {
+    64 + 64 * (gl_ext_mul() + gl_ext_square() + 2)
+}
+
+fn rshift(n: usize) -> usize {
+    less_n_bits(n)
+}
+
+fn reverse_bits(n: usize) -> usize {
+    n
+}
+
+fn last_n_bits(n: usize) -> usize {
+    n
+}
+
+fn random_access2(n: usize, m: usize) -> usize {
+    // IsEqual plus the selection product per element.
+    3 * n * m
+}
+
+fn reduce(n: usize) -> usize {
+    n * (gl_ext_mul() + gl_ext_add())
+}
+
+fn num2bits_strict() -> usize {
+    // Num2Bits(254) and the AliasCheck comparison: 127 bit pairs and a Num2Bits(135).
+    254 + 127 + 135
+}
+
+fn poseidon_bn() -> usize {
+    // PoseidonEx(4, 4): 8 full rounds and 60 partial rounds of x^5, plus four Num2Bits_strict.
+    (8 * 5 + 60) * 3 + 4 * num2bits_strict()
+}
+
+fn poseidon_gl() -> usize {
+    let sbox = 3 * (1 + gl_reduce(66)) + 1;
+    let mds = 12 * gl_reduce(74);
+    8 * (12 * sbox + mds) + 22 * (sbox + mds)
+}
+
+fn keccak_gl(n_outs: usize) -> usize {
+    // 24 rounds of theta (1600 + 320 + 1280 products) and chi (2 * 1600 products) per digest.
+    12 * 64 + (n_outs + 3) / 4 * 24 * 6400 + n_outs * gl_reduce_bits()
+}
+
+fn sha256_gl(n_outs: usize) -> usize {
+    // The 97 byte message takes two blocks of circomlib's Sha256compression, about 30k each.
+    12 * 64 + (n_outs + 3) / 4 * 2 * 30_000 + n_outs * gl_reduce_bits()
+}
+
+/// `Permutation`, with the hasher selected by `HASHER()`.
+fn permutation(hasher: CircomHasherKind, n_outs: usize) -> usize {
+    match hasher {
+        CircomHasherKind::PoseidonBN128 => poseidon_bn(),
+        CircomHasherKind::Keccak256 => keccak_gl(n_outs),
+        CircomHasherKind::Sha256 => sha256_gl(n_outs),
+    }
+}
+
+fn hash_no_pad(hasher: CircomHasherKind, n_inputs: usize, n_outputs: usize) -> usize {
+    (n_inputs + 7) / 8 * permutation(hasher, 12) + n_outputs * gl_reduce(1)
+}
+
+fn hash_no_pad_gl(n_inputs: usize) -> usize {
+    (n_inputs + 7) / 8 * poseidon_gl()
+}
+
+/// `HashPublicInputs`, with the hasher selected by `PUBLIC_INPUT_HASHER()`.
+fn hash_public_inputs(public_input_hasher: CircomPublicInputHasherKind, n_inputs: usize) -> usize {
+    match public_input_hasher {
+        CircomPublicInputHasherKind::PoseidonGoldilocks => hash_no_pad_gl(n_inputs),
+        CircomPublicInputHasherKind::PoseidonBN128 => {
+            hash_no_pad(CircomHasherKind::PoseidonBN128, n_inputs, 4)
+        }
+        CircomPublicInputHasherKind::Keccak256 => {
+            hash_no_pad(CircomHasherKind::Keccak256, n_inputs, 4)
+        }
+    }
+}
+
+fn get_merkle_proof_to_cap(hasher: CircomHasherKind, n_leaf: usize, n_proof: usize) -> usize {
+    hash_no_pad(hasher, n_leaf, 4) + n_proof * (permutation(hasher, 4) + 1 + 16)
+}
+
+fn cal_barycentric_weights(arity: usize) -> usize {
+    arity * (arity - 1) * gl_sub() + arity * (arity - 2) * gl_mul() + arity * gl_inv()
+}
+
+fn fri_step(
+    hasher: CircomHasherKind,
+    arity: usize,
+    num_siblings: usize,
+    num_commit_cap: usize,
+) -> usize {
+    let arity_bits = arity.trailing_zeros() as usize;
+    get_merkle_proof_to_cap(hasher, 2 * arity, num_siblings)
+        + random_access2(num_commit_cap, 4)
+        + rshift(arity_bits)
+        + last_n_bits(arity_bits)
+        + random_access2(arity, 2)
+        + reverse_bits(arity_bits)
+        + gl_exp()
+        + arity * gl_mul()
+        + cal_barycentric_weights(arity)
+        + gl_ext_sub()
+        + (arity - 1) * (gl_ext_sub() + gl_ext_mul())
+        + arity
+            * (gl_ext_add()
+                + gl_ext_sub()
+                + gl_ext_div()
+                + gl_ext_mul()
+                + reverse_bits(arity_bits)
+                + random_access2(arity, 2))
+        + gl_ext_mul()
+        + gl_exp_power_of_2(arity_bits)
+}
+
+fn sponge_inputs(num_inputs: usize) -> usize {
+    num_inputs.max(8)
+}
+
+/// Estimates proof bytes and Circom constraints for each part of a proof with the shape `conf`,
+/// verified with `hasher` and `public_input_hasher`, see `CircomHasher`, by the verifier
+/// generated with `options`. Proof bytes only depend on `conf`.
+pub fn estimate_cost(
+    conf: &VerifierConfig,
+    hasher: CircomHasherKind,
+    public_input_hasher: CircomPublicInputHasherKind,
+    options: &CircomVerifierOptions,
+) -> CostReport {
+    let mut report = CostReport::default();
+
+    let cap_height = conf.num_wires_cap.trailing_zeros() as usize;
+    let log_lde_size = conf.num_fri_query_init_wires_p + cap_height;
+    let num_challenges = conf.num_openings_plonk_zs;
+
+    report.push(
+        "caps",
+        (conf.num_wires_cap + conf.num_plonk_zs_partial_products_cap + conf.num_quotient_polys_cap)
+            * conf.hash_size,
+        hash_no_pad(hasher, 8 + conf.num_wires_cap * 4, 12)
+            + hash_no_pad(
+                hasher,
+                sponge_inputs(conf.num_plonk_zs_partial_products_cap * 4),
+                12,
+            )
+            + hash_no_pad(hasher, sponge_inputs(conf.num_quotient_polys_cap * 4), 12),
+    );
+
+    let num_openings = conf.num_openings_constants
+        + conf.num_openings_plonk_sigmas
+        + conf.num_openings_wires
+        + conf.num_openings_plonk_zs
+        + conf.num_openings_plonk_zs_next
+        + conf.num_openings_partial_products
+        + conf.num_openings_quotient_polys;
+    report.push(
+        "openings",
+        num_openings * conf.ext_field_size,
+        hash_no_pad(hasher, num_openings * 2, 12) + reduce(num_openings) + gl_ext_mul(),
+    );
+
+    report.push(
+        "fri_commit_caps",
+        conf.num_fri_commit_round * conf.fri_commit_merkle_cap_height * conf.hash_size,
+        conf.num_fri_commit_round
+            * hash_no_pad(
+                hasher,
+                sponge_inputs(conf.fri_commit_merkle_cap_height * 4),
+                12,
+            ),
+    );
+
+    let init_trees = [
+        (
+            conf.num_fri_query_init_constants_sigmas_v,
+            conf.num_fri_query_init_constants_sigmas_p,
+        ),
+        (
+            conf.num_fri_query_init_wires_v,
+            conf.num_fri_query_init_wires_p,
+        ),
+        (
+            conf.num_fri_query_init_zs_partial_v,
+            conf.num_fri_query_init_zs_partial_p,
+        ),
+        (
+            conf.num_fri_query_init_quotient_v,
+            conf.num_fri_query_init_quotient_p,
+        ),
+    ];
+    let init_bytes = init_trees
+        .iter()
+        .map(|&(v, p)| v * conf.field_size + p * conf.hash_size + conf.merkle_height_size)
+        .sum::<usize>();
+    let init_constraints = init_trees
+        .iter()
+        .map(|&(v, p)| {
+            get_merkle_proof_to_cap(hasher, v, p) + random_access2(conf.num_wires_cap, 4)
+        })
+        .sum::<usize>()
+        + reverse_bits(log_lde_size)
+        + gl_exp()
+        + gl_mul()
+        + init_trees.iter().map(|&(v, _)| reduce(v)).sum::<usize>()
+        + reduce(num_challenges)
+        + 4 * gl_ext_sub()
+        + 2 * gl_ext_div()
+        + gl_ext_exp()
+        + 2 * gl_ext_mul()
+        + gl_ext_add();
+
+    let steps = [
+        (conf.num_fri_query_step0_v, conf.num_fri_query_step0_p),
+        (conf.num_fri_query_step1_v, conf.num_fri_query_step1_p),
+    ];
+    let steps_bytes = steps
+        .iter()
+        .map(|&(v, p)| v * conf.ext_field_size + p * conf.hash_size + conf.merkle_height_size)
+        .sum::<usize>();
+    let steps_constraints = steps
+        .iter()
+        .map(|&(v, p)| fri_step(hasher, v, p, conf.fri_commit_merkle_cap_height))
+        .sum::<usize>();
+
+    for i in 0..conf.num_fri_query_round {
+        report.push(
+            &format!("fri_query_init[{}]", i),
+            init_bytes,
+            init_constraints,
+        );
+        report.push(
+            &format!("fri_query_steps[{}]", i),
+            steps_bytes,
+            steps_constraints,
+        );
+    }
+
+    report.push(
+        "fri_final_poly",
+        conf.num_fri_final_poly_ext_v * conf.ext_field_size,
+        conf.num_fri_query_round * conf.num_fri_final_poly_ext_v * (gl_ext_mul() + gl_ext_add()),
+    );
+
+    // The grinding check is bounded by a 64-bit range check; the final transcript batches also
+    // produce the query indices.
+    let num_index_batches = (conf.num_fri_query_round.saturating_sub(7) + 7) / 8;
+    report.push(
+        "fri_pow",
+        conf.field_size,
+        hash_no_pad(hasher, conf.num_fri_final_poly_ext_v * 2 + 1, 12)
+            + less_n_bits(64)
+            + num_index_batches * hash_no_pad(hasher, 8, 12)
+            + conf.num_fri_query_round * last_n_bits(log_lde_size),
+    );
+
+    report.push(
+        "public_inputs",
+        conf.num_public_inputs * conf.field_size,
+        hash_public_inputs(public_input_hasher, conf.num_public_inputs),
+    );
+
+    // The sigma cap has as many elements as the wires cap.
+    let num_key_inputs = 4 + 4 * conf.num_wires_cap;
+    if options.verifier_key == CircomVerifierKey::Hash {
+        report.push(
+            "verifier_key_hash",
+            0,
+            hash_no_pad(CircomHasherKind::PoseidonBN128, num_key_inputs, 4),
+        );
+    }
+
+    if !options.range_check_inputs {
+        return report;
+    }
+    // CanonicalProofInputs, which also checks the verifier key when it is an input.
+    let num_query_inputs = conf.num_fri_query_init_constants_sigmas_v
+        + conf.num_fri_query_init_wires_v
+        + conf.num_fri_query_init_zs_partial_v
+        + conf.num_fri_query_init_quotient_v
+        + 2 * (conf.num_fri_query_step0_v + conf.num_fri_query_step1_v)
+        + 4 * (conf.num_fri_query_init_constants_sigmas_p
+            + conf.num_fri_query_init_wires_p
+            + conf.num_fri_query_init_zs_partial_p
+            + conf.num_fri_query_init_quotient_p
+            + conf.num_fri_query_step0_p
+            + conf.num_fri_query_step1_p);
+    let num_inputs = 4
+        * (conf.num_wires_cap
+            + conf.num_plonk_zs_partial_products_cap
+            + conf.num_quotient_polys_cap)
+        + 2 * num_openings
+        + 4 * conf.num_fri_commit_round * conf.fri_commit_merkle_cap_height
+        + conf.num_fri_query_round * num_query_inputs
+        + 2 * conf.num_fri_final_poly_ext_v
+        + 1
+        + conf.num_public_inputs
+        + if options.verifier_key == CircomVerifierKey::Constant {
+            0
+        } else {
+            num_key_inputs
+        };
+    report.push("input_range_checks", 0, num_inputs * gl_canonical());
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::config::{CircomHasherKind, CircomPublicInputHasherKind};
+    use crate::cost::{estimate_cost, gl_canonical};
+    use crate::verifier::{CircomVerifierKey, CircomVerifierOptions, VerifierConfig};
+
+    #[test]
+    fn test_estimate_cost() -> Result<()> {
+        let conf: VerifierConfig =
+            serde_json::from_str(&std::fs::read_to_string("./circom/test/data/conf.json")?)?;
+        let options = CircomVerifierOptions::default();
+        let report = estimate_cost(
+            &conf,
+            CircomHasherKind::PoseidonBN128,
+            CircomPublicInputHasherKind::PoseidonGoldilocks,
+            &options,
+        );
+
+        // Proof size of the recursive proof in the README.
+        assert_eq!(report.total_proof_bytes(), 127728);
+        assert_eq!(
+            report
+                .sections
+                .iter()
+                .filter(|s| s.name.starts_with("fri_query_init"))
+                .count(),
+            conf.num_fri_query_round
+        );
+
+        // circom reports 29442900 non-linear constraints for this conf in the README, compiled
+        // before the input range checks. The estimate leaves out the gate constraints and the
+        // vanishing polynomial, which are 10 to 15 percent of it.
+        let compiled = 29442900;
+        let estimated = report.total_constraints()
+            - report
+                .sections
+                .iter()
+                .find(|s| s.name == "input_range_checks")
+                .unwrap()
+                .constraints;
+        assert!(estimated < compiled * 9 / 10);
+        assert!(estimated > compiled * 17 / 20);
+
+        for hasher in [CircomHasherKind::Keccak256, CircomHasherKind::Sha256] {
+            let report = estimate_cost(
+                &conf,
+                hasher,
+                CircomPublicInputHasherKind::PoseidonGoldilocks,
+                &options,
+            );
+            assert_eq!(report.total_proof_bytes(), 127728);
+            assert!(report.total_constraints() > 5 * compiled);
+        }
+
+        let unchecked = estimate_cost(
+            &conf,
+            CircomHasherKind::PoseidonBN128,
+            CircomPublicInputHasherKind::PoseidonGoldilocks,
+            &CircomVerifierOptions {
+                range_check_inputs: false,
+                ..CircomVerifierOptions::default()
+            },
+        );
+        assert_eq!(unchecked.total_constraints(), estimated);
+
+        // The verifier key inputs are range checked too, and hashed in the hashed key mode.
+        let key_checks = (4 + 4 * conf.num_wires_cap) * gl_canonical();
+        let public_key = estimate_cost(
+            &conf,
+            CircomHasherKind::PoseidonBN128,
+            CircomPublicInputHasherKind::PoseidonGoldilocks,
+            &CircomVerifierOptions {
+                verifier_key: CircomVerifierKey::Public,
+                ..CircomVerifierOptions::default()
+            },
+        );
+        assert_eq!(
+            public_key.total_constraints(),
+            report.total_constraints() + key_checks
+        );
+        let hashed_key = estimate_cost(
+            &conf,
+            CircomHasherKind::PoseidonBN128,
+            CircomPublicInputHasherKind::PoseidonGoldilocks,
+            &CircomVerifierOptions {
+                verifier_key: CircomVerifierKey::Hash,
+                ..CircomVerifierOptions::default()
+            },
+        );
+        assert!(hashed_key.total_constraints() > public_key.total_constraints());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,397 @@
+//! Circom verifier fixtures for a table of named scenarios. Each scenario is generated into its
+//! own directory, so scenarios never overwrite each other's files, and `Fixture::check` tells
+//! whether committed fixtures are still what the code generates.
+//!
+//! `cargo run --release --bin fixtures -- --help` lists the scenarios.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use num::BigUint;
+use plonky2::field::extension::Extendable;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::Field;
+use plonky2::fri::reduction_strategies::FriReductionStrategy;
+use plonky2::fri::FriConfig;
+use plonky2::gates::noop::NoopGate;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::{CircuitConfig, CommonCircuitData, VerifierOnlyCircuitData};
+use plonky2::plonk::config::{GenericConfig, Hasher, PoseidonGoldilocksConfig};
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use serde_json::{json, Value};
+
+use crate::config::PoseidonBN128GoldilocksConfig;
+use crate::mutation::write_mutated_proofs;
+use crate::verifier::{
+    generate_circom_verifier, generate_proof_base64, generate_verifier_config, wrap_for_circom,
+    VerifierConfig, WrapOptions,
+};
+
+/// Directory of the committed fixtures, relative to the crate root.
+pub const FIXTURES_DIR: &str = "./circom/test/fixtures";
+
+/// The scenario installed in `circom/circuits` and `circom/test/data`.
+pub const INSTALLED_SCENARIO: Scenario = Scenario::Recursive;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Scenario {
+    /// A proof without public inputs, with the standard recursion config.
+    NoPublicInputs,
+    /// A proof with 4 public inputs, with the standard recursion config.
+    PublicInputs,
+    /// A proof wrapped by `wrap_for_circom` through two standard recursion layers, with its
+    /// `Plonky2Verifier.sol`. This is the verifier of `circom/circuits`.
+    Recursive,
+    /// A high-rate recursive proof, designed to be verifiable with fewer routed wires.
+    HighRate,
+    /// A final proof, optimized for size.
+    FinalSize,
+}
+
+impl Scenario {
+    pub const ALL: [Scenario; 5] = [
+        Scenario::NoPublicInputs,
+        Scenario::PublicInputs,
+        Scenario::Recursive,
+        Scenario::HighRate,
+        Scenario::FinalSize,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scenario::NoPublicInputs => "no_public_inputs",
+            Scenario::PublicInputs => "public_inputs",
+            Scenario::Recursive => "recursive",
+            Scenario::HighRate => "high_rate",
+            Scenario::FinalSize => "final_size",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|s| s.name() == name)
+            .ok_or_else(|| anyhow!("unknown scenario {}", name))
+    }
+
+    pub fn generate(&self) -> Result<Fixture> {
+        const D: usize = 2;
+        type C = PoseidonBN128GoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let standard_config = CircuitConfig::standard_recursion_config();
+        let high_rate_config = CircuitConfig {
+            fri_config: FriConfig {
+                rate_bits: 7,
+                proof_of_work_bits: 16,
+                num_query_rounds: 12,
+                ..standard_config.fri_config.clone()
+            },
+            ..standard_config.clone()
+        };
+        let final_config = CircuitConfig {
+            num_routed_wires: 37,
+            fri_config: FriConfig {
+                rate_bits: 8,
+                cap_height: 0,
+                proof_of_work_bits: 20,
+                reduction_strategy: FriReductionStrategy::MinSize(None),
+                num_query_rounds: 10,
+            },
+            ..high_rate_config.clone()
+        };
+
+        match self {
+            Scenario::NoPublicInputs => {
+                Fixture::from_proof(dummy_proof::<F, C, D>(&standard_config, 4_000, 0)?)
+            }
+            Scenario::PublicInputs => {
+                Fixture::from_proof(dummy_proof::<F, C, D>(&standard_config, 4_000, 4)?)
+            }
+            Scenario::Recursive => {
+                let (proof, vd, cd) =
+                    dummy_proof::<F, PoseidonGoldilocksConfig, D>(&standard_config, 4_000, 4)?;
+                let options = WrapOptions {
+                    layer_configs: vec![standard_config.clone(), standard_config],
+                    solidity_wrapper: true,
+                    ..WrapOptions::default()
+                };
+                let wrapped = wrap_for_circom::<F, C, _, D>(proof, vd, cd, &options)?;
+                Ok(Fixture {
+                    proof: wrapped.proof,
+                    conf: wrapped.conf,
+                    circom_constants: wrapped.circom_constants,
+                    circom_gates: wrapped.circom_gates,
+                    proof_json: wrapped.proof_json,
+                    solidity_wrapper: wrapped.solidity_wrapper,
+                })
+            }
+            Scenario::HighRate => {
+                Fixture::from_proof(dummy_proof::<F, C, D>(&high_rate_config, 4_000, 4)?)
+            }
+            Scenario::FinalSize => {
+                Fixture::from_proof(dummy_proof::<F, C, D>(&final_config, 4_000, 0)?)
+            }
+        }
+    }
+}
+
+/// Everything the Circom tests need for one scenario.
+pub struct Fixture {
+    pub proof: ProofWithPublicInputs<GoldilocksField, PoseidonBN128GoldilocksConfig, 2>,
+    pub conf: VerifierConfig,
+    /// Contents of `constants.circom`.
+    pub circom_constants: String,
+    /// Contents of `gates.circom`.
+    pub circom_gates: String,
+    /// Input of the Circom verifier.
+    pub proof_json: String,
+    /// Contents of `Plonky2Verifier.sol`, for wrapped proofs.
+    pub solidity_wrapper: Option<String>,
+}
+
+impl Fixture {
+    fn from_proof(
+        (proof, vd, cd): (
+            ProofWithPublicInputs<GoldilocksField, PoseidonBN128GoldilocksConfig, 2>,
+            VerifierOnlyCircuitData<PoseidonBN128GoldilocksConfig, 2>,
+            CommonCircuitData<GoldilocksField, 2>,
+        ),
+    ) -> Result<Self> {
+        let conf = generate_verifier_config(&proof)?;
+        let (circom_constants, circom_gates) = generate_circom_verifier(&conf, &cd, &vd)?;
+        let proof_json = generate_proof_base64(&proof, &conf)?;
+        Ok(Self {
+            proof,
+            conf,
+            circom_constants,
+            circom_gates,
+            proof_json,
+            solidity_wrapper: None,
+        })
+    }
+
+    /// File names and contents of the fixture.
+    pub fn files(&self) -> Result<Vec<(&'static str, String)>> {
+        let mut files = vec![
+            ("constants.circom", self.circom_constants.clone()),
+            ("gates.circom", self.circom_gates.clone()),
+            ("conf.json", serde_json::to_string_pretty(&self.conf)?),
+            ("proof.json", self.proof_json.clone()),
+        ];
+        if let Some(solidity_wrapper) = &self.solidity_wrapper {
+            files.push(("Plonky2Verifier.sol", solidity_wrapper.clone()));
+        }
+        Ok(files)
+    }
+
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+        for (name, contents) in self.files()? {
+            fs::write(dir.join(name), contents)?;
+        }
+        Ok(())
+    }
+
+    /// Copies the fixture to where the Circom tests and `e2e_tests` read it:
+    /// `circom/circuits/{constants,gates}.circom` and `circom/test/data/{conf,proof}.json`, and
+    /// writes the mutated proofs of `write_mutated_proofs` to `circom/test/data/mutations`.
+    pub fn install(&self, circom_dir: &Path) -> Result<()> {
+        fs::create_dir_all(circom_dir.join("circuits"))?;
+        fs::create_dir_all(circom_dir.join("test/data"))?;
+        for (name, contents) in self.files()? {
+            if let Some(path) = installed_path(circom_dir, name) {
+                fs::write(path, contents)?;
+            }
+        }
+        write_mutated_proofs(
+            &self.proof,
+            &self.conf,
+            &circom_dir.join("test/data/mutations"),
+        )?;
+        Ok(())
+    }
+
+    /// Fails unless `dir` holds this fixture. Proofs are not reproducible, since the proof of
+    /// work is searched in parallel, so `proof.json` only has to have the same shape.
+    pub fn check(&self, dir: &Path) -> Result<()> {
+        for (name, contents) in self.files()? {
+            check_file(&dir.join(name), name, &contents)?;
+        }
+        Ok(())
+    }
+
+    /// Like `check`, for the copy `install` made in `circom_dir`.
+    pub fn check_installed(&self, circom_dir: &Path) -> Result<()> {
+        for (name, contents) in self.files()? {
+            if let Some(path) = installed_path(circom_dir, name) {
+                check_file(&path, name, &contents)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where `install` copies the fixture file `name`, if it does.
+fn installed_path(circom_dir: &Path, name: &str) -> Option<PathBuf> {
+    match name {
+        "constants.circom" | "gates.circom" => Some(circom_dir.join("circuits").join(name)),
+        "conf.json" | "proof.json" => Some(circom_dir.join("test/data").join(name)),
+        _ => None,
+    }
+}
+
+fn check_file(path: &Path, name: &str, contents: &str) -> Result<()> {
+    let committed =
+        fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    let up_to_date = if name == "proof.json" {
+        same_shape(
+            &serde_json::from_str(&committed)?,
+            &serde_json::from_str(contents)?,
+        )
+    } else {
+        committed == contents
+    };
+    if !up_to_date {
+        bail!("{} is out of date", path.display());
+    }
+    Ok(())
+}
+
+/// Whether `a` and `b` have the same keys, array lengths and value types.
+fn same_shape(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_shape(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(k, a)| b.get(k).is_some_and(|b| same_shape(a, b)))
+        }
+        (Value::String(_), Value::String(_))
+        | (Value::Number(_), Value::Number(_))
+        | (Value::Bool(_), Value::Bool(_))
+        | (Value::Null, Value::Null) => true,
+        _ => false,
+    }
+}
+
+/// The committed `snarkjs generatecall` output of the proof verified by `e2e_tests/hardhat`.
+pub const SNARKJS_CALL: &str = "./circom/e2e_tests/hardhat/test/public.txt";
+
+/// Reads `SNARKJS_CALL`. Returns its values in order, the proof in the snarkjs `proof.json`
+/// format, where the coordinates of `pi_b` are swapped back, and the public signals.
+pub fn snarkjs_call() -> Result<(Vec<BigUint>, String, Vec<u64>)> {
+    let call = fs::read_to_string(SNARKJS_CALL)?
+        .split(|c: char| "[]\", \n".contains(c))
+        .filter(|x| !x.is_empty())
+        .map(|x| {
+            BigUint::parse_bytes(x.trim_start_matches("0x").as_bytes(), 16)
+                .ok_or_else(|| anyhow!("invalid value {} in {}", x, SNARKJS_CALL))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    ensure!(call.len() > 8, "{} has no public signals", SNARKJS_CALL);
+    let v = |i: usize| call[i].to_string();
+    let proof_json = json!({
+        "pi_a": [v(0), v(1), "1"],
+        "pi_b": [[v(3), v(2)], [v(5), v(4)], ["1", "0"]],
+        "pi_c": [v(6), v(7), "1"],
+        "protocol": "groth16",
+        "curve": "bn128"
+    })
+    .to_string();
+    let public_signals = call[8..]
+        .iter()
+        .map(|x| u64::try_from(x).map_err(|_| anyhow!("public signal {} is not a u64", x)))
+        .collect::<Result<_>>()?;
+    Ok((call, proof_json, public_signals))
+}
+
+/// Creates a dummy proof which should have roughly `num_dummy_gates` gates.
+pub fn dummy_proof<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>(
+    config: &CircuitConfig,
+    num_dummy_gates: u64,
+    num_public_inputs: u64,
+) -> Result<(
+    ProofWithPublicInputs<F, C, D>,
+    VerifierOnlyCircuitData<C, D>,
+    CommonCircuitData<F, D>,
+)>
+where
+    [(); C::Hasher::HASH_SIZE]:,
+{
+    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+    for _ in 0..num_dummy_gates {
+        builder.add_gate(NoopGate, vec![]);
+    }
+    let mut pi = Vec::new();
+    if num_public_inputs > 0 {
+        pi = builder.add_virtual_targets(num_public_inputs as usize);
+        builder.register_public_inputs(&pi);
+    }
+
+    let data = builder.build::<C>();
+    let mut inputs = PartialWitness::new();
+    if num_public_inputs > 0 {
+        for i in 0..num_public_inputs {
+            inputs.set_target(pi[i as usize], F::from_canonical_u64(i));
+        }
+    }
+    let proof = data.prove(inputs)?;
+    data.verify(proof.clone())?;
+
+    Ok((proof, data.verifier_only, data.common))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::fixtures::{same_shape, Scenario};
+
+    #[test]
+    fn test_check_fixture() -> Result<()> {
+        let fixture = Scenario::PublicInputs.generate()?;
+        let tmp = tempfile::tempdir()?;
+        let dir = tmp.path().join("fixtures");
+        fixture.write(&dir)?;
+        fixture.check(&dir)?;
+
+        // A new proof of the same circuit is still up to date.
+        Scenario::PublicInputs.generate()?.check(&dir)?;
+
+        std::fs::write(dir.join("gates.circom"), "")?;
+        assert!(fixture.check(&dir).is_err());
+
+        let circom_dir = tmp.path().join("circom");
+        fixture.install(&circom_dir)?;
+        fixture.check_installed(&circom_dir)?;
+        assert!(circom_dir
+            .join("test/data/mutations/mutations.json")
+            .is_file());
+        std::fs::write(circom_dir.join("circuits/constants.circom"), "")?;
+        assert!(fixture.check_installed(&circom_dir).is_err());
+        assert!(Scenario::from_name("recursive")? == Scenario::Recursive);
+        assert!(Scenario::from_name("recursion").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_same_shape() {
+        let a = serde_json::json!({"x": ["1", "2"], "y": 3});
+        assert!(same_shape(
+            &a,
+            &serde_json::json!({"y": 4, "x": ["5", "6"]})
+        ));
+        assert!(!same_shape(&a, &serde_json::json!({"x": ["1"], "y": 3})));
+        assert!(!same_shape(&a, &serde_json::json!({"x": ["1", 2], "y": 3})));
+        assert!(!same_shape(
+            &a,
+            &serde_json::json!({"x": ["1", "2"], "z": 3})
+        ));
+    }
+}
@@ -0,0 +1,663 @@
+//! Groth16 over BN254 with arkworks, as a replacement for the snarkjs and rapidsnark steps of
+//! `circom/e2e_tests/run.sh`.
+//!
+//! Keys come from a snarkjs `.zkey`, so proofs verify against the `verification_key.json` and
+//! `verifier.sol` exported from the same file, or from `Groth16Key::setup` for local testing.
+//! Proofs and public inputs are written in the snarkjs `proof.json` and `public.json` formats.
+
+use std::io::Read;
+
+use anyhow::{anyhow, ensure, Result};
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, BigInteger256, PrimeField, Zero};
+use ark_groth16::r1cs_to_qap::{LibsnarkReduction, R1CSToQAP};
+use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_poly::EvaluationDomain;
+use ark_relations::r1cs::{
+    ConstraintMatrices, ConstraintSynthesizer, ConstraintSystemRef, LinearCombination,
+    SynthesisError, Variable,
+};
+use ark_std::rand::{CryptoRng, RngCore};
+use ark_std::UniformRand;
+use num::BigUint;
+use serde_json::{json, Value};
+
+use crate::snarkjs::{BinFile, R1cs, Reader, Wtns};
+
+pub(crate) fn to_biguint<F: PrimeField>(x: F) -> BigUint {
+    BigUint::from_bytes_le(&x.into_bigint().to_bytes_le())
+}
+
+fn to_fr(x: &BigUint) -> Fr {
+    Fr::from_le_bytes_mod_order(&x.to_bytes_le())
+}
+
+/// The reduction snarkjs uses: `H` is evaluated on the odd powers of a `2n`-th root of unity
+/// instead of being interpolated, which is what the `H` points of a `.zkey` commit to.
+pub struct CircomReduction;
+
+impl R1CSToQAP for CircomReduction {
+    fn instance_map_with_evaluation<F: PrimeField, D: EvaluationDomain<F>>(
+        cs: ConstraintSystemRef<F>,
+        t: &F,
+    ) -> Result<(Vec<F>, Vec<F>, Vec<F>, F, usize, usize), SynthesisError> {
+        LibsnarkReduction::instance_map_with_evaluation::<F, D>(cs, t)
+    }
+
+    fn witness_map_from_matrices<F: PrimeField, D: EvaluationDomain<F>>(
+        matrices: &ConstraintMatrices<F>,
+        num_inputs: usize,
+        num_constraints: usize,
+        full_assignment: &[F],
+    ) -> Result<Vec<F>, SynthesisError> {
+        let evaluate = |lc: &[(F, usize)]| {
+            lc.iter().fold(F::zero(), |acc, (coeff, i)| {
+                acc + *coeff * full_assignment[*i]
+            })
+        };
+        let domain =
+            D::new(num_constraints + num_inputs).ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+        let domain_size = domain.size();
+
+        let mut a = vec![F::zero(); domain_size];
+        let mut b = vec![F::zero(); domain_size];
+        for (i, (a_i, b_i)) in matrices.a.iter().zip(&matrices.b).enumerate() {
+            a[i] = evaluate(a_i);
+            b[i] = evaluate(b_i);
+        }
+        a[num_constraints..num_constraints + num_inputs]
+            .clone_from_slice(&full_assignment[..num_inputs]);
+        // The witness satisfies the constraints, so C is A * B on the constraint rows.
+        let mut c = vec![F::zero(); domain_size];
+        for i in 0..num_constraints {
+            c[i] = a[i] * b[i];
+        }
+
+        let root_of_unity = D::new(2 * domain_size)
+            .ok_or(SynthesisError::PolynomialDegreeTooLarge)?
+            .element(1);
+        for v in [&mut a, &mut b, &mut c] {
+            domain.ifft_in_place(v);
+            D::distribute_powers_and_mul_by_const(v, root_of_unity, F::one());
+            domain.fft_in_place(v);
+        }
+
+        let mut ab = domain.mul_polynomials_in_evaluation_domain(&a, &b);
+        for (ab_i, c_i) in ab.iter_mut().zip(c) {
+            *ab_i -= c_i;
+        }
+        Ok(ab)
+    }
+
+    fn h_query_scalars<F: PrimeField, D: EvaluationDomain<F>>(
+        max_power: usize,
+        t: F,
+        zt: F,
+        delta_inverse: F,
+    ) -> Result<Vec<F>, SynthesisError> {
+        LibsnarkReduction::h_query_scalars::<F, D>(max_power, t, zt, delta_inverse)
+    }
+}
+
+/// An `R1cs` as an arkworks circuit without assignments, for key generation.
+struct R1csCircuit<'a>(&'a R1cs);
+
+impl<'a> ConstraintSynthesizer<Fr> for R1csCircuit<'a> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let num_public = (self.0.num_pub_out + self.0.num_pub_in) as usize;
+        let mut variables = vec![Variable::One];
+        for i in 1..self.0.num_wires as usize {
+            let value = || Err::<Fr, _>(SynthesisError::AssignmentMissing);
+            variables.push(if i <= num_public {
+                cs.new_input_variable(value)?
+            } else {
+                cs.new_witness_variable(value)?
+            });
+        }
+
+        let lc = |terms: &crate::snarkjs::LinearCombination| {
+            LinearCombination(
+                terms
+                    .iter()
+                    .map(|(wire, coeff)| (to_fr(coeff), variables[*wire as usize]))
+                    .collect(),
+            )
+        };
+        for constraint in &self.0.constraints {
+            cs.enforce_constraint(lc(&constraint.a), lc(&constraint.b), lc(&constraint.c))?;
+        }
+        Ok(())
+    }
+}
+
+/// A Groth16 proving key, with the reduction matching how it was generated.
+pub struct Groth16Key {
+    pub pk: ProvingKey<Bn254>,
+    zkey: Option<ZkeyShape>,
+}
+
+/// The circuit size a `.zkey` was generated for.
+struct ZkeyShape {
+    num_vars: usize,
+    domain_size: usize,
+}
+
+impl Groth16Key {
+    /// Reads the proving key of a snarkjs Groth16 `.zkey`.
+    ///
+    /// Points are stored in Montgomery form. The constraint coefficients of the `.zkey` are not
+    /// read, `prove` takes them from the `.r1cs` instead.
+    pub fn read_zkey(r: impl Read) -> Result<Self> {
+        fn fq(r: &mut Reader) -> Result<Fq> {
+            let limbs = r.bytes(32)?;
+            let limbs = [0, 1, 2, 3]
+                .map(|i| u64::from_le_bytes(limbs[i * 8..i * 8 + 8].try_into().unwrap()));
+            Ok(Fq::new_unchecked(BigInteger256::new(limbs)))
+        }
+        fn g1(r: &mut Reader) -> Result<G1Affine> {
+            let (x, y) = (fq(r)?, fq(r)?);
+            if x.is_zero() && y.is_zero() {
+                return Ok(G1Affine::identity());
+            }
+            let p = G1Affine::new_unchecked(x, y);
+            ensure!(p.is_on_curve(), "invalid G1 point in zkey");
+            Ok(p)
+        }
+        fn g2(r: &mut Reader) -> Result<G2Affine> {
+            let x = Fq2::new(fq(r)?, fq(r)?);
+            let y = Fq2::new(fq(r)?, fq(r)?);
+            if x.is_zero() && y.is_zero() {
+                return Ok(G2Affine::identity());
+            }
+            let p = G2Affine::new_unchecked(x, y);
+            ensure!(
+                p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve(),
+                "invalid G2 point in zkey"
+            );
+            Ok(p)
+        }
+        fn points<T>(
+            r: &mut Reader,
+            n: usize,
+            read: fn(&mut Reader) -> Result<T>,
+        ) -> Result<Vec<T>> {
+            (0..n).map(|_| read(r)).collect()
+        }
+
+        let file = BinFile::read(r, b"zkey", 1)?;
+        ensure!(
+            file.section(1)?.u32()? == 1,
+            "only Groth16 zkeys are supported"
+        );
+
+        let mut header = file.section(2)?;
+        let n8q = header.u32()? as usize;
+        ensure!(
+            header.field(n8q)? == to_biguint(-Fq::from(1u32)) + 1u32,
+            "zkey is not over BN254"
+        );
+        let n8r = header.u32()? as usize;
+        ensure!(
+            header.field(n8r)? == to_biguint(-Fr::from(1u32)) + 1u32,
+            "zkey is not over BN254"
+        );
+        let num_vars = header.u32()? as usize;
+        let num_public = header.u32()? as usize;
+        let domain_size = header.u32()? as usize;
+        let num_private = num_vars.checked_sub(num_public + 1).ok_or_else(|| {
+            anyhow!(
+                "zkey has {} variables, fewer than its {} public inputs",
+                num_vars,
+                num_public
+            )
+        })?;
+        let alpha_g1 = g1(&mut header)?;
+        let beta_g1 = g1(&mut header)?;
+        let beta_g2 = g2(&mut header)?;
+        let gamma_g2 = g2(&mut header)?;
+        let delta_g1 = g1(&mut header)?;
+        let delta_g2 = g2(&mut header)?;
+
+        let pk = ProvingKey {
+            vk: VerifyingKey {
+                alpha_g1,
+                beta_g2,
+                gamma_g2,
+                delta_g2,
+                gamma_abc_g1: points(&mut file.section(3)?, num_public + 1, g1)?,
+            },
+            beta_g1,
+            delta_g1,
+            a_query: points(&mut file.section(5)?, num_vars, g1)?,
+            b_g1_query: points(&mut file.section(6)?, num_vars, g1)?,
+            b_g2_query: points(&mut file.section(7)?, num_vars, g2)?,
+            l_query: points(&mut file.section(8)?, num_private, g1)?,
+            h_query: points(&mut file.section(9)?, domain_size, g1)?,
+        };
+        Ok(Self {
+            pk,
+            zkey: Some(ZkeyShape {
+                num_vars,
+                domain_size,
+            }),
+        })
+    }
+
+    /// Generates a key for `r1cs` from randomness known to the caller. This is only sound for
+    /// testing: production keys need a `.zkey` from a powers of tau and phase 2 ceremony.
+    pub fn setup<R: RngCore + CryptoRng>(r1cs: &R1cs, rng: &mut R) -> Result<Self> {
+        let pk =
+            Groth16::<Bn254>::generate_random_parameters_with_reduction(R1csCircuit(r1cs), rng)?;
+        Ok(Self { pk, zkey: None })
+    }
+
+    /// Proves that `wtns` satisfies `r1cs`. Returns the proof and the public inputs, the public
+    /// outputs followed by the public inputs of the circuit.
+    pub fn prove<R: RngCore + CryptoRng>(
+        &self,
+        r1cs: &R1cs,
+        wtns: &Wtns,
+        rng: &mut R,
+    ) -> Result<(Proof<Bn254>, Vec<Fr>)> {
+        ensure!(
+            r1cs.prime == to_biguint(-Fr::from(1u32)) + 1u32,
+            "circuit is not over BN254"
+        );
+        r1cs.check(wtns, &Default::default())?;
+        let num_inputs = (1 + r1cs.num_pub_out + r1cs.num_pub_in) as usize;
+        ensure!(
+            self.pk.vk.gamma_abc_g1.len() == num_inputs,
+            "key has {} public inputs, the circuit {}",
+            self.pk.vk.gamma_abc_g1.len() - 1,
+            num_inputs - 1
+        );
+        if let Some(zkey) = &self.zkey {
+            // snarkjs adds one constraint per public input and for the constant wire.
+            let domain_size = (r1cs.constraints.len() + num_inputs).next_power_of_two();
+            ensure!(
+                zkey.num_vars == r1cs.num_wires as usize && zkey.domain_size == domain_size,
+                "zkey has {} variables and domain size {}, the circuit {} and {}",
+                zkey.num_vars,
+                zkey.domain_size,
+                r1cs.num_wires,
+                domain_size
+            );
+        }
+
+        let full_assignment = wtns.witness.iter().map(to_fr).collect::<Vec<_>>();
+        let matrix = |lc: &crate::snarkjs::LinearCombination| {
+            lc.iter()
+                .map(|(wire, coeff)| (to_fr(coeff), *wire as usize))
+                .collect::<Vec<_>>()
+        };
+        let a = r1cs
+            .constraints
+            .iter()
+            .map(|c| matrix(&c.a))
+            .collect::<Vec<_>>();
+        let b = r1cs
+            .constraints
+            .iter()
+            .map(|c| matrix(&c.b))
+            .collect::<Vec<_>>();
+        let c = r1cs
+            .constraints
+            .iter()
+            .map(|c| matrix(&c.c))
+            .collect::<Vec<_>>();
+        let matrices = ConstraintMatrices {
+            num_instance_variables: num_inputs,
+            num_witness_variables: r1cs.num_wires as usize - num_inputs,
+            num_constraints: r1cs.constraints.len(),
+            a_num_non_zero: a.iter().map(Vec::len).sum(),
+            b_num_non_zero: b.iter().map(Vec::len).sum(),
+            c_num_non_zero: c.iter().map(Vec::len).sum(),
+            a,
+            b,
+            c,
+        };
+
+        let (r, s) = (Fr::rand(rng), Fr::rand(rng));
+        let proof = if self.zkey.is_some() {
+            Groth16::<Bn254, CircomReduction>::create_proof_with_reduction_and_matrices(
+                &self.pk,
+                r,
+                s,
+                &matrices,
+                num_inputs,
+                matrices.num_constraints,
+                &full_assignment,
+            )?
+        } else {
+            Groth16::<Bn254, LibsnarkReduction>::create_proof_with_reduction_and_matrices(
+                &self.pk,
+                r,
+                s,
+                &matrices,
+                num_inputs,
+                matrices.num_constraints,
+                &full_assignment,
+            )?
+        };
+        Ok((proof, full_assignment[1..num_inputs].to_vec()))
+    }
+}
+
+/// Verifies `proof` for `public_inputs`, in the order `prove` returns them.
+pub fn verify(
+    vk: &VerifyingKey<Bn254>,
+    proof: &Proof<Bn254>,
+    public_inputs: &[Fr],
+) -> Result<bool> {
+    let pvk = ark_groth16::prepare_verifying_key(vk);
+    Groth16::<Bn254>::verify_proof(&pvk, proof, public_inputs)
+        .map_err(|e| anyhow!("cannot verify the proof: {}", e))
+}
+
+fn fq_from_json(v: &Value) -> Result<Fq> {
+    let x: BigUint = v
+        .as_str()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| anyhow!("invalid coordinate {}", v))?;
+    ensure!(
+        x < to_biguint(-Fq::from(1u32)) + 1u32,
+        "coordinate {} is not below the BN254 base field order",
+        x
+    );
+    Ok(Fq::from_le_bytes_mod_order(&x.to_bytes_le()))
+}
+
+fn g1_from_json(v: &Value) -> Result<G1Affine> {
+    ensure!(v[2] == "1", "G1 point {} is not affine", v);
+    let p = G1Affine::new_unchecked(fq_from_json(&v[0])?, fq_from_json(&v[1])?);
+    ensure!(p.is_on_curve(), "G1 point {} is not on the curve", v);
+    Ok(p)
+}
+
+fn g2_from_json(v: &Value) -> Result<G2Affine> {
+    ensure!(v[2] == json!(["1", "0"]), "G2 point {} is not affine", v);
+    let p = G2Affine::new_unchecked(
+        Fq2::new(fq_from_json(&v[0][0])?, fq_from_json(&v[0][1])?),
+        Fq2::new(fq_from_json(&v[1][0])?, fq_from_json(&v[1][1])?),
+    );
+    ensure!(
+        p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve(),
+        "G2 point {} is not in the curve subgroup",
+        v
+    );
+    Ok(p)
+}
+
+fn g1_to_json(p: &G1Affine) -> Value {
+    json!([
+        to_biguint(p.x).to_string(),
+        to_biguint(p.y).to_string(),
+        "1"
+    ])
+}
+
+fn g2_to_json(p: &G2Affine) -> Value {
+    let fq = |x: Fq| to_biguint(x).to_string();
+    json!([
+        [fq(p.x.c0), fq(p.x.c1)],
+        [fq(p.y.c0), fq(p.y.c1)],
+        ["1", "0"]
+    ])
+}
+
+/// A verification key in the snarkjs `verification_key.json` format, without the precomputed
+/// `vk_alphabeta_12`.
+pub fn verifying_key_to_json(vk: &VerifyingKey<Bn254>) -> String {
+    json!({
+        "protocol": "groth16",
+        "curve": "bn128",
+        "nPublic": vk.gamma_abc_g1.len().saturating_sub(1),
+        "vk_alpha_1": g1_to_json(&vk.alpha_g1),
+        "vk_beta_2": g2_to_json(&vk.beta_g2),
+        "vk_gamma_2": g2_to_json(&vk.gamma_g2),
+        "vk_delta_2": g2_to_json(&vk.delta_g2),
+        "IC": vk.gamma_abc_g1.iter().map(g1_to_json).collect::<Vec<_>>()
+    })
+    .to_string()
+}
+
+/// Reads a snarkjs `verification_key.json`.
+pub fn verifying_key_from_json(vk_json: &str) -> Result<VerifyingKey<Bn254>> {
+    let vk: Value = serde_json::from_str(vk_json)?;
+    ensure!(
+        vk["protocol"] == "groth16" && vk["curve"] == "bn128",
+        "not a Groth16 BN254 verification key"
+    );
+    let ic = vk["IC"]
+        .as_array()
+        .ok_or_else(|| anyhow!("verification key has no IC"))?;
+    // IC[0] is the constant term, which every key has.
+    ensure!(!ic.is_empty(), "verification key has an empty IC");
+    Ok(VerifyingKey {
+        alpha_g1: g1_from_json(&vk["vk_alpha_1"])?,
+        beta_g2: g2_from_json(&vk["vk_beta_2"])?,
+        gamma_g2: g2_from_json(&vk["vk_gamma_2"])?,
+        delta_g2: g2_from_json(&vk["vk_delta_2"])?,
+        gamma_abc_g1: ic.iter().map(g1_from_json).collect::<Result<_>>()?,
+    })
+}
+
+/// A proof in the snarkjs `proof.json` format, with projective coordinates.
+pub fn proof_to_json(proof: &Proof<Bn254>) -> String {
+    let fq = |x: Fq| to_biguint(x).to_string();
+    json!({
+        "pi_a": [fq(proof.a.x), fq(proof.a.y), "1"],
+        "pi_b": [
+            [fq(proof.b.x.c0), fq(proof.b.x.c1)],
+            [fq(proof.b.y.c0), fq(proof.b.y.c1)],
+            ["1", "0"]
+        ],
+        "pi_c": [fq(proof.c.x), fq(proof.c.y), "1"],
+        "protocol": "groth16",
+        "curve": "bn128"
+    })
+    .to_string()
+}
+
+/// Reads a snarkjs or rapidsnark `proof.json`.
+pub fn proof_from_json(proof_json: &str) -> Result<Proof<Bn254>> {
+    let proof: Value = serde_json::from_str(proof_json)?;
+    Ok(Proof {
+        a: g1_from_json(&proof["pi_a"])?,
+        b: g2_from_json(&proof["pi_b"])?,
+        c: g1_from_json(&proof["pi_c"])?,
+    })
+}
+
+/// The check of `verifyProof` in the snarkjs `verifier.sol`, for relayers to run before sending
+/// a transaction. `public_signals` are in the order of `verifier::circom_public_signals`.
+pub fn verify_groth16_bn254(
+    vk_json: &str,
+    proof_json: &str,
+    public_signals: &[u64],
+) -> Result<bool> {
+    let vk = verifying_key_from_json(vk_json)?;
+    // verifier-bad-input in verifier.sol. Goldilocks signals are always below the scalar field
+    // order, the other input check of the contract.
+    ensure!(
+        public_signals.len() + 1 == vk.gamma_abc_g1.len(),
+        "verification key has {} public signals, got {}",
+        vk.gamma_abc_g1.len() - 1,
+        public_signals.len()
+    );
+    let proof = proof_from_json(proof_json)?;
+    let public_inputs = public_signals
+        .iter()
+        .map(|x| Fr::from(*x))
+        .collect::<Vec<_>>();
+    verify(&vk, &proof, &public_inputs)
+}
+
+/// Public inputs in the snarkjs `public.json` format.
+pub fn public_inputs_to_json(public_inputs: &[Fr]) -> String {
+    json!(public_inputs
+        .iter()
+        .map(|x| to_biguint(*x).to_string())
+        .collect::<Vec<_>>())
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fr;
+    use num::BigUint;
+    use serde_json::json;
+
+    use crate::fixtures::snarkjs_call;
+    use crate::groth16::{
+        proof_to_json, public_inputs_to_json, to_biguint, verify, verify_groth16_bn254,
+        verifying_key_from_json, verifying_key_to_json, Groth16Key, ZkeyShape,
+    };
+    use crate::snarkjs::{Constraint, R1cs, Wtns};
+
+    #[test]
+    fn test_prove_and_verify() -> anyhow::Result<()> {
+        // out <== x * y, with public out.
+        let prime = to_biguint(-Fr::from(1u32)) + 1u32;
+        let r1cs = R1cs {
+            num_wires: 4,
+            num_pub_out: 1,
+            num_pub_in: 0,
+            num_prv_in: 2,
+            num_labels: 4,
+            constraints: vec![Constraint {
+                a: vec![(2, 1u32.into())],
+                b: vec![(3, 1u32.into())],
+                c: vec![(1, 1u32.into())],
+            }],
+            wire_to_label: vec![0, 1, 2, 3],
+            prime: prime.clone(),
+        };
+        let wtns = Wtns {
+            prime,
+            witness: [1u64, 6, 2, 3].map(BigUint::from).to_vec(),
+        };
+
+        let rng = &mut ark_std::test_rng();
+        let key = Groth16Key::setup(&r1cs, rng)?;
+        let (proof, public_inputs) = key.prove(&r1cs, &wtns, rng)?;
+        assert_eq!(public_inputs, vec![Fr::from(6u32)]);
+        assert!(verify(&key.pk.vk, &proof, &public_inputs)?);
+        assert!(!verify(&key.pk.vk, &proof, &[Fr::from(7u32)])?);
+
+        let mut bad = wtns.clone();
+        bad.witness[1] = 7u32.into();
+        assert!(key.prove(&r1cs, &bad, rng).is_err());
+
+        // A zkey for another circuit with the same public inputs is rejected.
+        let wrong_zkey = Groth16Key {
+            pk: key.pk.clone(),
+            zkey: Some(ZkeyShape {
+                num_vars: 5,
+                domain_size: 4,
+            }),
+        };
+        assert!(wrong_zkey.prove(&r1cs, &wtns, rng).is_err());
+
+        let proof_json: serde_json::Value = serde_json::from_str(&proof_to_json(&proof))?;
+        assert_eq!(proof_json["protocol"], "groth16");
+        assert_eq!(proof_json["pi_b"][2], serde_json::json!(["1", "0"]));
+        assert_eq!(public_inputs_to_json(&public_inputs), r#"["6"]"#);
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_groth16_bn254() -> anyhow::Result<()> {
+        // out <== x * y + in, with public out and in.
+        let prime = to_biguint(-Fr::from(1u32)) + 1u32;
+        let r1cs = R1cs {
+            num_wires: 6,
+            num_pub_out: 1,
+            num_pub_in: 1,
+            num_prv_in: 2,
+            num_labels: 6,
+            constraints: vec![
+                Constraint {
+                    a: vec![(3, 1u32.into())],
+                    b: vec![(4, 1u32.into())],
+                    c: vec![(5, 1u32.into())],
+                },
+                Constraint {
+                    a: vec![(0, 1u32.into())],
+                    b: vec![(5, 1u32.into()), (2, 1u32.into())],
+                    c: vec![(1, 1u32.into())],
+                },
+            ],
+            wire_to_label: vec![0, 1, 2, 3, 4, 5],
+            prime: prime.clone(),
+        };
+        let wtns = Wtns {
+            prime,
+            witness: [1u64, 11, 5, 2, 3, 6].map(BigUint::from).to_vec(),
+        };
+
+        let rng = &mut ark_std::test_rng();
+        let key = Groth16Key::setup(&r1cs, rng)?;
+        let (proof, _) = key.prove(&r1cs, &wtns, rng)?;
+        let vk_json = verifying_key_to_json(&key.pk.vk);
+        assert_eq!(verifying_key_from_json(&vk_json)?, key.pk.vk);
+        let proof_json = proof_to_json(&proof);
+
+        assert!(verify_groth16_bn254(&vk_json, &proof_json, &[11, 5])?);
+        assert!(!verify_groth16_bn254(&vk_json, &proof_json, &[5, 11])?);
+        assert!(verify_groth16_bn254(&vk_json, &proof_json, &[11]).is_err());
+        let mut no_ic: serde_json::Value = serde_json::from_str(&vk_json)?;
+        no_ic["IC"] = serde_json::json!([]);
+        assert!(verify_groth16_bn254(&no_ic.to_string(), &proof_json, &[]).is_err());
+
+        let mut bad_proof: serde_json::Value = serde_json::from_str(&proof_json)?;
+        bad_proof["pi_a"][1] = "1".into();
+        assert!(verify_groth16_bn254(&vk_json, &bad_proof.to_string(), &[11, 5]).is_err());
+        Ok(())
+    }
+
+    /// The verifying key of the snarkjs `verifier.sol` of `e2e_tests/hardhat`, in the
+    /// `verification_key.json` format.
+    fn hardhat_verifying_key() -> anyhow::Result<String> {
+        let sol = std::fs::read_to_string("./circom/e2e_tests/hardhat/contracts/verifier.sol")?;
+        let body = sol.split("function verifyingKey()").nth(1).unwrap();
+        let body = body.split("\n    }\n").next().unwrap();
+        let v = body
+            .lines()
+            .map(|l| l.replace(|c: char| "[](),; ".contains(c), ""))
+            .filter(|l| !l.is_empty() && l.chars().all(|c| c.is_ascii_digit()))
+            .collect::<Vec<_>>();
+        let g1 = |i: usize| json!([v[i], v[i + 1], "1"]);
+        // G2 coordinates are [c1, c0] in Solidity.
+        let g2 = |i: usize| json!([[v[i + 1], v[i]], [v[i + 3], v[i + 2]], ["1", "0"]]);
+        Ok(json!({
+            "protocol": "groth16",
+            "curve": "bn128",
+            "nPublic": (v.len() - 16) / 2,
+            "vk_alpha_1": g1(0),
+            "vk_beta_2": g2(2),
+            "vk_gamma_2": g2(6),
+            "vk_delta_2": g2(10),
+            "IC": (14..v.len()).step_by(2).map(g1).collect::<Vec<_>>()
+        })
+        .to_string())
+    }
+
+    #[test]
+    fn test_verify_snarkjs_proof() -> anyhow::Result<()> {
+        let vk_json = hardhat_verifying_key()?;
+        let (_, proof_json, public_signals) = snarkjs_call()?;
+        assert_eq!(public_signals.len(), 68);
+        assert!(verify_groth16_bn254(
+            &vk_json,
+            &proof_json,
+            &public_signals
+        )?);
+
+        let mut wrong = public_signals.clone();
+        wrong[67] += 1;
+        assert!(!verify_groth16_bn254(&vk_json, &proof_json, &wrong)?);
+        Ok(())
+    }
+}
@@ -3,4 +3,15 @@
 extern crate core;
 
 pub mod config;
+pub mod cost;
+pub mod fixtures;
+#[cfg(feature = "groth16")]
+pub mod groth16;
+pub mod mutation;
+pub mod optimizer;
+pub mod poseidon_bn128;
+pub mod security;
+pub mod snarkjs;
+pub mod solidity;
 pub mod verifier;
+pub mod witness;
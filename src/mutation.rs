@@ -0,0 +1,220 @@
+//! Corrupted copies of a valid proof, for soundness tests of the Circom verifier and of any other
+//! verifier of the same proofs.
+//!
+//! Every mutation keeps the shape and canonical encoding of the proof, so the Circom witness can
+//! still be computed and only the verification checks fail. The challenges are derived from the
+//! mutated transcript, so a corrupted commitment or opening already fails the first check that
+//! uses the challenges. `expected_failure` names the first check that fails, which is the same for
+//! the Circom and the plonky2 verifier since both run their checks in the same order, and where
+//! the witness computation of the Circom verifier stops.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::{HashOut, RichField};
+use plonky2::plonk::config::{GenericConfig, Hasher};
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use serde_json::json;
+
+use crate::config::CircomHasher;
+use crate::verifier::{generate_proof_base64, VerifierConfig};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProofMutation {
+    /// Adds one to the first element of the wires cap.
+    FlippedCap,
+    /// Adds one to the opening of the first wire at zeta.
+    WrongOpening,
+    /// Adds one to the first Merkle sibling of the wires tree in the first query.
+    BadMerkleSibling,
+    /// Adds one to the proof of work witness.
+    WrongPowWitness,
+    /// Swaps the first two public inputs, if they differ.
+    SwappedPublicInputs,
+    /// Adds one to the constant coefficient of the final FRI polynomial.
+    WrongFinalPoly,
+}
+
+impl ProofMutation {
+    pub const ALL: [ProofMutation; 6] = [
+        ProofMutation::FlippedCap,
+        ProofMutation::WrongOpening,
+        ProofMutation::BadMerkleSibling,
+        ProofMutation::WrongPowWitness,
+        ProofMutation::SwappedPublicInputs,
+        ProofMutation::WrongFinalPoly,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProofMutation::FlippedCap => "flipped_cap",
+            ProofMutation::WrongOpening => "wrong_opening",
+            ProofMutation::BadMerkleSibling => "bad_merkle_sibling",
+            ProofMutation::WrongPowWitness => "wrong_pow_witness",
+            ProofMutation::SwappedPublicInputs => "swapped_public_input",
+            ProofMutation::WrongFinalPoly => "wrong_final_poly",
+        }
+    }
+
+    /// The first check that rejects the mutated proof, for proofs with FRI proof of work:
+    /// - `check_zeta`: the vanishing polynomial identity at zeta, `CheckZeta`. Everything
+    ///   observed before zeta changes it.
+    /// - `fri_pow`: the proof of work response has too few leading zeros. The final polynomial
+    ///   is observed before the response.
+    /// - `merkle_proof`: an opening does not hash up to its cap in `VerifyFriProofWithKey`.
+    pub fn expected_failure(&self) -> &'static str {
+        match self {
+            ProofMutation::FlippedCap
+            | ProofMutation::WrongOpening
+            | ProofMutation::SwappedPublicInputs => "check_zeta",
+            ProofMutation::WrongPowWitness | ProofMutation::WrongFinalPoly => "fri_pow",
+            ProofMutation::BadMerkleSibling => "merkle_proof",
+        }
+    }
+
+    /// Applies the mutation to `proof`. Returns `false` and leaves `proof` unchanged if the
+    /// proof has nothing to mutate, like a single public input.
+    pub fn apply<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>(
+        &self,
+        proof: &mut ProofWithPublicInputs<F, C, D>,
+    ) -> bool
+    where
+        C::Hasher: Hasher<F, Hash = HashOut<F>>,
+    {
+        let fri_proof = &mut proof.proof.opening_proof;
+        match self {
+            ProofMutation::FlippedCap => {
+                proof.proof.wires_cap.0[0].elements[0] += F::ONE;
+            }
+            ProofMutation::WrongOpening => {
+                proof.proof.openings.wires[0] += F::Extension::ONE;
+            }
+            ProofMutation::BadMerkleSibling => {
+                let merkle_proof = &mut fri_proof.query_round_proofs[0]
+                    .initial_trees_proof
+                    .evals_proofs[1]
+                    .1;
+                match merkle_proof.siblings.first_mut() {
+                    Some(sibling) => sibling.elements[0] += F::ONE,
+                    None => return false,
+                }
+            }
+            ProofMutation::WrongPowWitness => {
+                fri_proof.pow_witness += F::ONE;
+            }
+            ProofMutation::SwappedPublicInputs => {
+                let pis = &mut proof.public_inputs;
+                if pis.len() < 2 || pis[0] == pis[1] {
+                    return false;
+                }
+                pis.swap(0, 1);
+            }
+            ProofMutation::WrongFinalPoly => {
+                fri_proof.final_poly.coeffs[0] += F::Extension::ONE;
+            }
+        }
+        true
+    }
+}
+
+/// Writes the Circom input of every applicable mutation of `proof` to `<dir>/<name>.json`, and
+/// `<dir>/mutations.json` listing each file with its `expected_failure`.
+pub fn write_mutated_proofs<
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    const D: usize,
+>(
+    proof: &ProofWithPublicInputs<F, C, D>,
+    conf: &VerifierConfig,
+    dir: &Path,
+) -> Result<Vec<ProofMutation>>
+where
+    C::Hasher: CircomHasher<F>,
+{
+    fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    let mut applied = vec![];
+    let mut manifest = vec![];
+    for mutation in ProofMutation::ALL {
+        let mut mutated = proof.clone();
+        if !mutation.apply(&mut mutated) {
+            continue;
+        }
+        let file = format!("{}.json", mutation.name());
+        fs::write(dir.join(&file), generate_proof_base64(&mutated, conf)?)?;
+        manifest.push(json!({
+            "mutation": mutation.name(),
+            "file": file,
+            "expected_failure": mutation.expected_failure(),
+        }));
+        applied.push(mutation);
+    }
+    fs::write(
+        dir.join("mutations.json"),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::types::Field;
+    use plonky2::gates::noop::NoopGate;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::GenericConfig;
+
+    use crate::config::PoseidonBN128GoldilocksConfig;
+    use crate::mutation::{write_mutated_proofs, ProofMutation};
+    use crate::verifier::generate_verifier_config;
+
+    #[test]
+    fn test_mutated_proofs_are_rejected() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonBN128GoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+        // Enough gates for the two FRI query steps the Circom verifier expects.
+        for _ in 0..4_000 {
+            builder.add_gate(NoopGate, vec![]);
+        }
+        let pi = builder.add_virtual_targets(2);
+        builder.register_public_inputs(&pi);
+        let data = builder.build::<C>();
+        let mut inputs = PartialWitness::new();
+        inputs.set_target(pi[0], F::ONE);
+        inputs.set_target(pi[1], F::TWO);
+        let proof = data.prove(inputs)?;
+        data.verify(proof.clone())?;
+
+        let verifier_data = data.verifier_data();
+        for mutation in ProofMutation::ALL {
+            let mut mutated = proof.clone();
+            assert!(mutation.apply(&mut mutated), "{:?}", mutation);
+            assert!(
+                verifier_data.verify(mutated).is_err(),
+                "{:?} was accepted",
+                mutation
+            );
+        }
+
+        let conf = generate_verifier_config(&proof)?;
+        let dir = tempfile::tempdir()?;
+        let applied = write_mutated_proofs(&proof, &conf, dir.path())?;
+        assert_eq!(applied, ProofMutation::ALL);
+        let manifest: Vec<serde_json::Value> =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join("mutations.json"))?)?;
+        let swapped = manifest
+            .iter()
+            .find(|m| m["mutation"] == "swapped_public_input")
+            .unwrap();
+        assert_eq!(swapped["expected_failure"], "check_zeta");
+        assert!(dir.path().join("wrong_final_poly.json").is_file());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,324 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{ensure, Result};
+use plonky2::field::extension::Extendable;
+use plonky2::fri::reduction_strategies::FriReductionStrategy;
+use plonky2::fri::FriConfig;
+use plonky2::hash::hash_types::RichField;
+use plonky2::hash::hashing::SPONGE_WIDTH;
+use plonky2::plonk::circuit_data::{CircuitConfig, CommonCircuitData, VerifierOnlyCircuitData};
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig, Hasher};
+use plonky2::plonk::proof::ProofWithPublicInputs;
+
+use crate::config::CircomHasher;
+use crate::cost::estimate_cost;
+use crate::security::fri_security_bits;
+use crate::verifier::{
+    generate_verifier_config_from_common, recursive_circuit, recursive_proof,
+    CircomVerifierOptions, RecursionLayer,
+};
+
+/// Search space and ranking weights of `find_wrapper_config`.
+#[derive(Clone, Debug)]
+pub struct OptimizerOptions {
+    /// Conjectured security of the final proof, as computed by `fri_security_bits`.
+    pub target_security_bits: usize,
+    /// Padding of the final layer, as in `recursive_proof`.
+    pub min_degree_bits: Option<usize>,
+    pub rate_bits: Vec<usize>,
+    pub cap_heights: Vec<usize>,
+    pub proof_of_work_bits: Vec<u32>,
+    pub reduction_strategies: Vec<FriReductionStrategy>,
+    /// Number of candidates, cheapest by constraint count first, that are actually proven to
+    /// measure the plonky2 proving time.
+    pub num_measured: usize,
+    /// How many Circom constraints one second of plonky2 proving is worth when ranking.
+    pub constraints_per_second: f64,
+}
+
+impl Default for OptimizerOptions {
+    fn default() -> Self {
+        let mut reduction_strategies = vec![FriReductionStrategy::MinSize(None)];
+        for arity_bits in 1..=4 {
+            for final_poly_bits in 0..=6 {
+                reduction_strategies.push(FriReductionStrategy::ConstantArityBits(
+                    arity_bits,
+                    final_poly_bits,
+                ));
+            }
+        }
+        Self {
+            target_security_bits: 100,
+            min_degree_bits: None,
+            rate_bits: (3..=8).collect(),
+            cap_heights: (0..=4).collect(),
+            proof_of_work_bits: vec![16, 20, 24],
+            reduction_strategies,
+            num_measured: 3,
+            constraints_per_second: 1_000_000.0,
+        }
+    }
+}
+
+/// A final layer `CircuitConfig` with its estimated and measured costs.
+#[derive(Clone, Debug)]
+pub struct WrapperCandidate {
+    pub config: CircuitConfig,
+    pub security_bits: usize,
+    pub estimated_constraints: usize,
+    pub proof_bytes: usize,
+    /// Only set for the candidates that were proven.
+    pub prove_time: Option<Duration>,
+    /// `prove_work` scaled to the proving time of the proven candidates, unset if none was.
+    pub estimated_prove_time: Option<Duration>,
+}
+
+impl WrapperCandidate {
+    /// The measured proving time if there is one, else the estimated one.
+    fn score(&self, constraints_per_second: f64) -> f64 {
+        let prove_secs = self
+            .prove_time
+            .or(self.estimated_prove_time)
+            .map_or(0.0, |t| t.as_secs_f64());
+        self.estimated_constraints as f64 + constraints_per_second * prove_secs
+    }
+}
+
+/// Relative plonky2 proving time of a circuit of `degree_bits` with `config`. It is dominated by
+/// the LDE of the wires, `2^(degree_bits + rate_bits)` rows of `num_wires` elements to transform
+/// and hash, and by the proof of work search, about `2^proof_of_work_bits` permutations.
+fn prove_work(config: &CircuitConfig, degree_bits: usize) -> f64 {
+    let lde_rows = (1u64 << (degree_bits + config.fri_config.rate_bits)) as f64;
+    let pow_hashes = (1u64 << config.fri_config.proof_of_work_bits) as f64;
+    lde_rows * config.num_wires as f64 + pow_hashes * SPONGE_WIDTH as f64
+}
+
+/// Searches the `FriConfig` of the final `recursive_proof` layer over `inner_proof` that reaches
+/// `options.target_security_bits` with the cheapest Circom verifier.
+///
+/// The `options.num_measured` candidates with the fewest estimated constraints are proven. The
+/// proving time of the others is estimated with `prove_work`, scaled to the measured ones, and
+/// all candidates are ranked by constraints plus proving time. The returned list is sorted, best
+/// first, and its first `config` can be used as is as the last of `WrapOptions::layer_configs`.
+///
+/// Only the scores of candidates with a `prove_time` rest on measured times; the others are only
+/// as comparable as `prove_work` is accurate. Without measured candidates, the ranking is by
+/// constraints alone.
+pub fn find_wrapper_config<
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    InnerC: GenericConfig<D, F = F>,
+    const D: usize,
+>(
+    inner_proof: &ProofWithPublicInputs<F, InnerC, D>,
+    inner_vd: &VerifierOnlyCircuitData<InnerC, D>,
+    inner_cd: &CommonCircuitData<F, D>,
+    base_config: &CircuitConfig,
+    options: &OptimizerOptions,
+) -> Result<Vec<WrapperCandidate>>
+where
+    C::Hasher: CircomHasher<F>,
+    InnerC::Hasher: AlgebraicHasher<F>,
+    [(); C::Hasher::HASH_SIZE]:,
+{
+    ensure!(
+        options.target_security_bits > 0,
+        "target security must be positive"
+    );
+
+    // The FRI parameters do not change the gates of the wrapper, so its degree is found once.
+    let base_layer = RecursionLayer {
+        min_degree_bits: options.min_degree_bits,
+        ..RecursionLayer::new(base_config)
+    };
+    let (data, _, _) =
+        recursive_circuit::<F, C, InnerC, D>(inner_vd, inner_cd, &base_layer, false)?;
+    let base_common = data.common;
+    let degree_bits = base_common.degree_bits();
+
+    let mut candidates = vec![];
+    for &rate_bits in &options.rate_bits {
+        if base_common.quotient_degree_factor > 1 << rate_bits {
+            continue;
+        }
+        for &proof_of_work_bits in &options.proof_of_work_bits {
+            let remaining = options
+                .target_security_bits
+                .saturating_sub(proof_of_work_bits as usize);
+            let num_query_rounds = (remaining + rate_bits - 1) / rate_bits;
+            for &cap_height in &options.cap_heights {
+                for reduction_strategy in &options.reduction_strategies {
+                    let fri_config = FriConfig {
+                        rate_bits,
+                        cap_height,
+                        proof_of_work_bits,
+                        reduction_strategy: reduction_strategy.clone(),
+                        num_query_rounds,
+                    };
+                    let fri_params = fri_config.fri_params(degree_bits, base_config.zero_knowledge);
+                    // The Circom templates only implement arities up to 2^4.
+                    if fri_params.reduction_arity_bits.iter().any(|&b| b > 4) {
+                        continue;
+                    }
+                    let config = CircuitConfig {
+                        fri_config,
+                        ..base_config.clone()
+                    };
+                    let common = CommonCircuitData {
+                        config: config.clone(),
+                        fri_params,
+                        ..base_common.clone()
+                    };
+                    let conf = match generate_verifier_config_from_common(&common) {
+                        Ok(conf) => conf,
+                        Err(_) => continue,
+                    };
+                    let security_bits = fri_security_bits::<F, D>(&config.fri_config);
+                    if security_bits < options.target_security_bits {
+                        continue;
+                    }
+                    let report = estimate_cost(
+                        &conf,
+                        <C::Hasher as CircomHasher<F>>::CIRCOM_HASHER,
+                        <C::Hasher as CircomHasher<F>>::PUBLIC_INPUT_HASHER,
+                        &CircomVerifierOptions::default(),
+                    );
+                    candidates.push(WrapperCandidate {
+                        config,
+                        security_bits,
+                        estimated_constraints: report.total_constraints(),
+                        proof_bytes: report.total_proof_bytes(),
+                        prove_time: None,
+                        estimated_prove_time: None,
+                    });
+                }
+            }
+        }
+    }
+    ensure!(
+        !candidates.is_empty(),
+        "no FRI config in the search space is supported by the Circom verifier"
+    );
+
+    candidates.sort_by_key(|c| c.estimated_constraints);
+    candidates.dedup_by(|a, b| {
+        a.estimated_constraints == b.estimated_constraints && a.proof_bytes == b.proof_bytes
+    });
+
+    for candidate in candidates.iter_mut().take(options.num_measured) {
+        let layer = RecursionLayer {
+            min_degree_bits: options.min_degree_bits,
+            ..RecursionLayer::new(&candidate.config)
+        };
+        let start = Instant::now();
+        recursive_proof::<F, C, InnerC, D>(
+            inner_proof.clone(),
+            inner_vd.clone(),
+            inner_cd.clone(),
+            &layer,
+            false,
+            false,
+        )?;
+        candidate.prove_time = Some(start.elapsed());
+    }
+
+    let (measured_secs, measured_work) = candidates
+        .iter()
+        .filter_map(|c| {
+            Some((
+                c.prove_time?.as_secs_f64(),
+                prove_work(&c.config, degree_bits),
+            ))
+        })
+        .fold((0.0, 0.0), |(s, w), (cs, cw)| (s + cs, w + cw));
+    if measured_work > 0.0 {
+        for candidate in candidates.iter_mut() {
+            let work = prove_work(&candidate.config, degree_bits);
+            candidate.estimated_prove_time = Some(Duration::from_secs_f64(
+                work * measured_secs / measured_work,
+            ));
+        }
+    }
+    candidates.sort_by(|a, b| {
+        a.score(options.constraints_per_second)
+            .total_cmp(&b.score(options.constraints_per_second))
+    });
+
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::types::Field;
+    use plonky2::gates::noop::NoopGate;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use crate::config::PoseidonBN128GoldilocksConfig;
+    use crate::optimizer::{find_wrapper_config, OptimizerOptions};
+    use crate::verifier::{
+        generate_verifier_config, generate_verifier_config_from_common, recursive_proof,
+        RecursionLayer,
+    };
+
+    #[test]
+    fn test_find_wrapper_config() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type CBn128 = PoseidonBN128GoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let standard_config = CircuitConfig::standard_recursion_config();
+
+        let mut builder = CircuitBuilder::<F, D>::new(standard_config.clone());
+        for _ in 0..4_000 {
+            builder.add_gate(NoopGate, vec![]);
+        }
+        let pi = builder.add_virtual_targets(4);
+        builder.register_public_inputs(&pi);
+        let data = builder.build::<C>();
+        let mut pw = PartialWitness::new();
+        for (i, &t) in pi.iter().enumerate() {
+            pw.set_target(t, F::from_canonical_usize(i));
+        }
+        let proof = data.prove(pw)?;
+
+        let options = OptimizerOptions {
+            num_measured: 1,
+            ..OptimizerOptions::default()
+        };
+        let candidates = find_wrapper_config::<F, CBn128, C, D>(
+            &proof,
+            &data.verifier_only,
+            &data.common,
+            &standard_config,
+            &options,
+        )?;
+        let best = &candidates[0];
+        assert!(best.security_bits >= options.target_security_bits);
+        assert_eq!(
+            candidates.iter().filter(|c| c.prove_time.is_some()).count(),
+            1
+        );
+        // Measured and unmeasured candidates are ranked on the same score.
+        assert!(candidates.iter().all(|c| c.estimated_prove_time.is_some()));
+        assert!(candidates.windows(2).all(|w| {
+            w[0].score(options.constraints_per_second) <= w[1].score(options.constraints_per_second)
+        }));
+
+        let (proof, _, cd) = recursive_proof::<F, CBn128, C, D>(
+            proof,
+            data.verifier_only,
+            data.common,
+            &RecursionLayer::new(&best.config),
+            false,
+            false,
+        )?;
+        let conf = generate_verifier_config(&proof)?;
+        assert_eq!(generate_verifier_config_from_common(&cd)?, conf);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,848 @@
+//! Pure Rust Poseidon permutation over the BN254 scalar field, with the parameters of
+//! circomlib's `PoseidonEx(4, 4)` and the Goldilocks packing of `Poseidon_BN` in
+//! `circom/circuits/poseidon.circom`.
+
+/// Number of BN254 elements in the state, `t` in circomlib.
+const WIDTH: usize = 5;
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 60;
+const ROUNDS: usize = FULL_ROUNDS + PARTIAL_ROUNDS;
+
+/// BN254 scalar field modulus, little-endian limbs.
+const MODULUS: [u64; 4] = [
+    0x43e1f593f0000001,
+    0x2833e84879b97091,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+];
+/// `-MODULUS^-1 mod 2^64`.
+const INV: u64 = 0xc2e1f593efffffff;
+/// `2^512 mod MODULUS`, converts into Montgomery form.
+const R2: [u64; 4] = [
+    0x1bb8e645ae216da7,
+    0x53fe3ab1e35c59e3,
+    0x8c49833d53bb8085,
+    0x0216d0b17f4e44a5,
+];
+
+/// Computes `a + b + carry`, returning the result and the new carry.
+#[inline(always)]
+const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let ret = (a as u128) + (b as u128) + (carry as u128);
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// Computes `a - (b + borrow)`, returning the result and the new borrow.
+#[inline(always)]
+const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let ret = (a as u128).wrapping_sub((b as u128) + ((borrow >> 63) as u128));
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// Computes `a + (b * c) + carry`, returning the result and the new carry.
+#[inline(always)]
+const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let ret = (a as u128) + ((b as u128) * (c as u128)) + (carry as u128);
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// An element of the BN254 scalar field in Montgomery form.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Fr([u64; 4]);
+
+impl Fr {
+    const ZERO: Fr = Fr([0; 4]);
+
+    const fn from_canonical(limbs: [u64; 4]) -> Fr {
+        Fr(limbs).mul(Fr(R2))
+    }
+
+    const fn to_canonical(self) -> [u64; 4] {
+        Fr::montgomery_reduce([self.0[0], self.0[1], self.0[2], self.0[3], 0, 0, 0, 0]).0
+    }
+
+    /// Subtracts the modulus once if `limbs` is not reduced; `limbs` must be below twice the
+    /// modulus.
+    const fn reduce_once(limbs: [u64; 4]) -> Fr {
+        let (d0, borrow) = sbb(limbs[0], MODULUS[0], 0);
+        let (d1, borrow) = sbb(limbs[1], MODULUS[1], borrow);
+        let (d2, borrow) = sbb(limbs[2], MODULUS[2], borrow);
+        let (d3, borrow) = sbb(limbs[3], MODULUS[3], borrow);
+        if borrow == 0 {
+            Fr([d0, d1, d2, d3])
+        } else {
+            Fr(limbs)
+        }
+    }
+
+    const fn add(self, rhs: Fr) -> Fr {
+        // The modulus is below 2^254, so the sum never overflows.
+        let (d0, carry) = adc(self.0[0], rhs.0[0], 0);
+        let (d1, carry) = adc(self.0[1], rhs.0[1], carry);
+        let (d2, carry) = adc(self.0[2], rhs.0[2], carry);
+        let (d3, _) = adc(self.0[3], rhs.0[3], carry);
+        Fr::reduce_once([d0, d1, d2, d3])
+    }
+
+    /// Montgomery multiplication, in the carry-free CIOS variant that applies because the top
+    /// limb of the modulus is below `2^63 - 1`.
+    #[inline(always)]
+    const fn mul(self, rhs: Fr) -> Fr {
+        let mut t = [0u64; 4];
+        let mut i = 0;
+        while i < 4 {
+            let (t0, mut carry_a) = mac(t[0], self.0[0], rhs.0[i], 0);
+            let m = t0.wrapping_mul(INV);
+            let (_, mut carry_m) = mac(t0, m, MODULUS[0], 0);
+            let mut j = 1;
+            while j < 4 {
+                let tj;
+                (tj, carry_a) = mac(t[j], self.0[j], rhs.0[i], carry_a);
+                (t[j - 1], carry_m) = mac(tj, m, MODULUS[j], carry_m);
+                j += 1;
+            }
+            t[3] = carry_a + carry_m;
+            i += 1;
+        }
+        Fr::reduce_once(t)
+    }
+
+    /// The full product of the Montgomery representations, to be summed before a single
+    /// `montgomery_reduce`.
+    #[inline(always)]
+    fn mul_wide(self, rhs: Fr) -> [u64; 8] {
+        let mut t = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0;
+            for j in 0..4 {
+                (t[i + j], carry) = mac(t[i + j], self.0[i], rhs.0[j], carry);
+            }
+            t[i + 4] = carry;
+        }
+        t
+    }
+
+    /// Divides `t` by `2^256` modulo the modulus; `t` must be below the modulus times `2^256`.
+    const fn montgomery_reduce(mut t: [u64; 8]) -> Fr {
+        let mut carry2 = 0;
+        let mut i = 0;
+        while i < 4 {
+            let k = t[i].wrapping_mul(INV);
+            let mut carry = 0;
+            let mut j = 0;
+            while j < 4 {
+                (t[i + j], carry) = mac(t[i + j], k, MODULUS[j], carry);
+                j += 1;
+            }
+            (t[i + 4], carry2) = adc(t[i + 4], carry, carry2);
+            i += 1;
+        }
+        Fr::reduce_once([t[4], t[5], t[6], t[7]])
+    }
+
+    fn pow5(self) -> Fr {
+        let x2 = self.mul(self);
+        x2.mul(x2).mul(self)
+    }
+}
+
+const fn to_montgomery<const N: usize>(limbs: [[u64; 4]; N]) -> [Fr; N] {
+    let mut ret = [Fr::ZERO; N];
+    let mut i = 0;
+    while i < N {
+        ret[i] = Fr::from_canonical(limbs[i]);
+        i += 1;
+    }
+    ret
+}
+
+static ARK_MONT: [Fr; ROUNDS * WIDTH] = to_montgomery(ARK);
+static MDS_MONT: [[Fr; WIDTH]; WIDTH] = [
+    to_montgomery(MDS[0]),
+    to_montgomery(MDS[1]),
+    to_montgomery(MDS[2]),
+    to_montgomery(MDS[3]),
+    to_montgomery(MDS[4]),
+];
+
+/// Permutes `N` states in lockstep, so that their independent multiplications overlap.
+fn poseidon<const N: usize>(mut states: [[Fr; WIDTH]; N]) -> [[Fr; WIDTH]; N] {
+    for r in 0..ROUNDS {
+        for state in states.iter_mut() {
+            for (i, x) in state.iter_mut().enumerate() {
+                *x = x.add(ARK_MONT[r * WIDTH + i]);
+            }
+        }
+        if (FULL_ROUNDS / 2..FULL_ROUNDS / 2 + PARTIAL_ROUNDS).contains(&r) {
+            for state in states.iter_mut() {
+                state[0] = state[0].pow5();
+            }
+        } else {
+            for x in states.iter_mut().flatten() {
+                *x = x.pow5();
+            }
+        }
+        for state in states.iter_mut() {
+            // The sum of WIDTH products stays below the modulus times 2^256, so every row is
+            // only reduced once.
+            let mut mixed = [Fr::ZERO; WIDTH];
+            for (y, row) in mixed.iter_mut().zip(MDS_MONT.iter()) {
+                let mut acc = [0u64; 8];
+                for (&m, &x) in row.iter().zip(state.iter()) {
+                    let product = m.mul_wide(x);
+                    let mut carry = 0;
+                    for k in 0..8 {
+                        (acc[k], carry) = adc(acc[k], product[k], carry);
+                    }
+                }
+                *y = Fr::montgomery_reduce(acc);
+            }
+            *state = mixed;
+        }
+    }
+    states
+}
+
+/// Permutes a Goldilocks sponge state given as canonical `u64`s, exactly like `Poseidon_BN(12)`.
+///
+/// Every BN254 input packs three Goldilocks elements as `a * 2^128 + b * 2^64 + c`, after an
+/// initial zero element. The first four output elements are split back into Goldilocks elements
+/// by `goldilocks_limbs`.
+pub fn permute(input: [u64; 12]) -> [u64; 12] {
+    let [state] = poseidon([to_state(input)]);
+    from_state(state)
+}
+
+/// Like `permute` on every state, four at a time.
+///
+/// There is no SIMD path: four lanes of AVX2 Montgomery multiplication on 32-bit limbs were
+/// measured about 1.7 times slower than the scalar one on 64-bit limbs.
+pub fn permute_many(states: &mut [[u64; 12]]) {
+    const BATCH: usize = 4;
+    let mut chunks = states.chunks_exact_mut(BATCH);
+    for chunk in &mut chunks {
+        let outputs = poseidon::<BATCH>(core::array::from_fn(|k| to_state(chunk[k])));
+        for (state, output) in chunk.iter_mut().zip(outputs) {
+            *state = from_state(output);
+        }
+    }
+    for state in chunks.into_remainder() {
+        *state = permute(*state);
+    }
+}
+
+fn to_state(input: [u64; 12]) -> [Fr; WIDTH] {
+    let mut state = [Fr::ZERO; WIDTH];
+    for (x, chunk) in state[1..].iter_mut().zip(input.chunks(3)) {
+        *x = Fr::from_canonical([chunk[2], chunk[1], chunk[0], 0]);
+    }
+    state
+}
+
+fn from_state(state: [Fr; WIDTH]) -> [u64; 12] {
+    let mut output = [0; 12];
+    for (chunk, x) in output.chunks_mut(3).zip(state) {
+        chunk.copy_from_slice(&goldilocks_limbs(x.to_canonical()));
+    }
+    output
+}
+
+/// The Goldilocks prime `2^64 - 2^32 + 1`.
+pub const GOLDILOCKS_ORDER: u64 = 0xffffffff00000001;
+
+/// Splits a canonical BN254 element like `Poseidon_BN` does with `Num2Bits_strict`: its 64-bit
+/// limbs 2, 1 and 0, in this order, each reduced to a canonical Goldilocks element. Bits 192 and
+/// above are dropped.
+pub fn goldilocks_limbs(limbs: [u64; 4]) -> [u64; 3] {
+    [limbs[2], limbs[1], limbs[0]].map(reduce_goldilocks)
+}
+
+/// Reduces a 64-bit limb modulo the Goldilocks prime. Since `2^64 < 2 * GOLDILOCKS_ORDER`, one
+/// subtraction is enough, and it is needed exactly when the high 32 bits are all ones and the
+/// low 32 bits are not all zeros, which is how `Poseidon_BN` checks it.
+pub fn reduce_goldilocks(x: u64) -> u64 {
+    if x >= GOLDILOCKS_ORDER {
+        x - GOLDILOCKS_ORDER
+    } else {
+        x
+    }
+}
+
+/// Round constants of circomlib `PoseidonEx` with `t = 5`, `ROUNDS * WIDTH` canonical elements.
+#[rustfmt::skip]
+const ARK: [[u64; 4]; ROUNDS * WIDTH] = [
+    [0x4f3c2bd81a6da891, 0xd889bb4ebd47c386, 0x7f53e29ccac98ed7, 0x0eb544fee2815dda],
+    [0xba733f28475128cb, 0xa197aeb12ea64713, 0xf02fdba7dd737fbc, 0x0554d736315b8662],
+    [0xf5087c58d5e8c2d4, 0x54907df0c0fb0035, 0xbcd748056307c377, 0x2f83b9df259b2b68],
+    [0x1915208f5aba9683, 0x61f15f8b41a75ef3, 0x2447ac83052451b4, 0x2ca70e2e8d7f39a1],
+    [0x4ec719cb83ecfea9, 0x994196f12ed22c5d, 0x91b04d7222271c94, 0x1cb5f9319be6a45e],
+    [0xb9ea08d18446c122, 0x21c7bb47b93750c2, 0xf8a42192de7ff616, 0x2eb4f99c69f966eb],
+    [0xd5f7d099e299520e, 0x0fc7da8b93ee13b6, 0xc5198169e405d9ea, 0x224a28e5a35385a7],
+    [0x23796b993bbd82f7, 0x36f33ecbd9a0f978, 0xd8afdd6afca49c30, 0x0f7411b465e600ee],
+    [0x0626f9ff5d90e4e3, 0x19b208ae3370f99a, 0xa2be7150392d8d98, 0x0f9d0d5aad2c9555],
+    [0x6a12d30702d6fba0, 0x9732b25259cf744b, 0x6f52a59538d32922, 0x1e9a96dc8292bb59],
+    [0xc2cd4d528fb3fe3c, 0x3cfe52eab4b945c6, 0x887d578c45555e59, 0x08780514ccd90380],
+    [0x59f119d629ccb5fc, 0xced64717e3556d5a, 0xc8149fa3f73ef8c2, 0x272498fced686c7a],
+    [0xe3d0ef8a782ef7df, 0x45bd350aff585f10, 0x4b7cb80930bd06eb, 0x01ef8f9dd7c93aac],
+    [0x97ea0ae754934d30, 0x8e886e64bf3c40aa, 0x4dc08f222b469b13, 0x045b9f59b6595e61],
+    [0xadf2584295d61c66, 0xa3e41e24e247a387, 0x9fd6f59d2a40ff8e, 0x0ac1e91c57d9da91],
+    [0xd12e68f04e62d134, 0x0fd67061aee99979, 0xc7f9a421353cd89d, 0x028a1621a94054b0],
+    [0xd02aae2a0dcd9dbc, 0x50c19c3fb3c96d09, 0x9632647ed059236e, 0x26b41802c071ea4c],
+    [0x4af34384aedb462b, 0x05c9de06758db6a9, 0xbaac2f63e468215e, 0x2fb5dda8072bb72c],
+    [0xdd05c56ee894d850, 0xad8ab8ba2a18d383, 0x44ff3547fd823249, 0x2212d3a0f5fccaf2],
+    [0x5cd0c7eed465e2e3, 0xa3276fdb19f44c01, 0x58e4dfaeea09be56, 0x1b041ad5b2f06842],
+    [0xa91e64afb1500eff, 0x144fb7e3ac14e846, 0x8eccff33e76fded3, 0x0a01776bb22f4b6b],
+    [0xf7936440810ace43, 0xa4f33ae8c15cf827, 0xf34d3f275066d549, 0x2b7b5674aaecc3cb],
+    [0x4c1b803381a3bdfd, 0x60b042257b78fc00, 0x4cf75779ed54b48c, 0x29d299b80cd4489e],
+    [0x19dde30424be401e, 0xa427110032b5e1dd, 0x57641c219d721a74, 0x1c46831d9a745293],
+    [0x739ae1d883e91269, 0x36296c0657674f80, 0xf37141dc34d578e0, 0x06d7626c953ccb72],
+    [0x0f108c71cda2930c, 0xdc5c440a3022cd96, 0xc54002748e0c410e, 0x28ffddc86f18c136],
+    [0xb8ec0619f6fbc5e9, 0xbe67f1b7ed2ab6ad, 0xf85deed09e400b17, 0x2e67f7ee5e4aa295],
+    [0xe7abf22c24e80f27, 0xca56859ef759e53c, 0x0e97f25114a79a2d, 0x26ce38fa636c9063],
+    [0xafacb4c4cfd35db1, 0x42cb3d16a1f72721, 0x4dd7a01d00a7ffec, 0x2e6e07c3c95bf7c3],
+    [0x384b1470da24d8cc, 0x8890d276612e1246, 0x5f91d7961c3a54fb, 0x2aa74f7597f0c9f4],
+    [0x2ec0ca639b7f73fe, 0x8a71313c1509183e, 0x2c7c090f668ab45b, 0x287d681a46a2faae],
+    [0x11e29f3305e73c04, 0xa5d3106ff565aa3b, 0xf4a40600528f3d7d, 0x212bd19df812eaae],
+    [0x3809c28404713504, 0xf97fd9740926dab9, 0x1aafb14b350eb860, 0x1154f7cf519186bf],
+    [0xda747e79661fc207, 0xbf5152905be36583, 0x4637810a4bd1b16f, 0x1dff6385cb31f1c2],
+    [0x63ea3c606b551e5c, 0x4011a34d54762528, 0xc081d34c44c18e42, 0x0e444582d22b4e76],
+    [0xff72d3aab7e4eff8, 0x815773e9c2846323, 0xabab6638328f02f1, 0x0323c9e433ba66c4],
+    [0xb70f2c6876a9c29d, 0x25b8cf002740112d, 0x193bba79cdec448f, 0x12746bbd71791059],
+    [0xfd049eb4438a2240, 0xd466c837cf50d73e, 0xfd9b9d3751842c75, 0x1173b7d112c2a798],
+    [0x5fdb4808703243da, 0xa8e5713b25026ebe, 0x76d1e555d7fed13d, 0x13d51c1090a1ad48],
+    [0xf4a58ebeb956baa1, 0xcb72743f0394efe7, 0xff8dcb7cbd2d9743, 0x00874c1344a4ad51],
+    [0xa32a562074fef08f, 0x0eea48d3546e97d6, 0x65ce236b07f244fa, 0x22df22131aaab858],
+    [0x8bf5eb05a919f155, 0x984524a59101e6c1, 0x8708b437a445fc3e, 0x0bf964d2dbd25b90],
+    [0x69b435b5fc502f32, 0x640b9d73a9ab298c, 0xa302be1f7f181e0e, 0x09b18d9b917a55bc],
+    [0xd6745a5067289e43, 0xfbbbc70a6365366d, 0xa4bfc1d5bf3dc05b, 0x094f5534444fae36],
+    [0x784fddc0dc23f01f, 0xa3e240c0da5701cb, 0x519fa6622af53a15, 0x2999bab1a5f25210],
+    [0x69575a11b03a3d23, 0x84301bce8a93d136, 0x1ca94db73710e880, 0x2f6898c07581f637],
+    [0x62174b1a6866fccb, 0x565deb1e8e5742f8, 0xec16d7e1318a4740, 0x07268eaaba08bc19],
+    [0xa6c9582c41a0529f, 0x62603e078e1c6689, 0x1339ff77113bc9eb, 0x186279b003454db0],
+    [0x0768e5524737172c, 0xb67e2cc5de9a2275, 0xe4915bdd04d3e5dd, 0x18a3f736509197d6],
+    [0x7b5987b87085671d, 0x725e2d4bcb2d3a00, 0x77cc1e2ed24c808c, 0x0a21fa1988cf38d8],
+    [0x0b36a135e785fba2, 0x8328c184a2c43bc0, 0x1faf5ef6a6462522, 0x15b285cbe26c467f],
+    [0x8cd28de3e779f161, 0x0b7775b7c902f578, 0x8c08b8c3f9806d56, 0x164b7062c4671cf0],
+    [0x7a24f651249baa70, 0x61c61d3d43b6e65d, 0x86d9865fe7e50ef3, 0x0890ba0819ac0a6f],
+    [0xcc2ee0aed543e922, 0xa627ac5cb0eb878c, 0xa42712e5a721e4ea, 0x2fbea4d65d7ed425],
+    [0x103d7f5f379abaaa, 0x7b70a58e854ab9b9, 0x540303a3b536f85e, 0x0492bf383c36fa55],
+    [0x6a969d56292dc24e, 0x1d6185a9ce85675f, 0x4e20251c565142d6, 0x05e91fe944e94410],
+    [0x7e380a76e36e6c1c, 0x995b9621e6e49c3b, 0x93d463cb041acad0, 0x12fe5c2029e4b338],
+    [0x06cd01dc6fa0784e, 0x1f2629fadc894969, 0x958f772392147413, 0x024154adf0255d47],
+    [0x8813c13457a45550, 0xd798815644f2bbde, 0x36ed2462a86bd0ba, 0x18824a09e6afaf4a],
+    [0xbb06983e3d5d58a5, 0xdf84a630af68d50b, 0xe9f255de0c3dbddd, 0x0c8b482dba0ad51b],
+    [0x441392878fdb05e6, 0x5a4fa67fcd6aaf86, 0x363e0a1667d3b67c, 0x17325fd0ab635871],
+    [0x1cc6d0e911fa402e, 0xe550773fa8d18bf7, 0x122f5af67b690f31, 0x050ae95f6d2f1519],
+    [0x5771ec84edc50c40, 0x4bbb6295f0756988, 0x038cb288d6263676, 0x0f0d139a0e81e943],
+    [0x9dbbca7b8e747cd6, 0x79b39ebc7a1b1c54, 0xf70fd2f2c0f93d1a, 0x1c0f8697795689cd],
+    [0xfe536a16dc1d81e6, 0x49be23a4b13598f9, 0x6d2bc2e048bc979e, 0x2bd0f940ad936b79],
+    [0x7ce54d1e96ee62cb, 0xa06ebb275e096d16, 0x4778c09a0053337f, 0x27eb1be27c9c4e93],
+    [0xf151be62548e2aea, 0x284fbd307d1f71b0, 0x8f96bdd3155a7ca3, 0x2e4889d830a67e5a],
+    [0x6064bbe6fcc1e305, 0xc9891f2cadc165db, 0x5d2ec5e9c5bd9983, 0x193fe3db0ab47d3c],
+    [0x37cb13027c83e525, 0x6e9661c009679e4e, 0xce415907ad0c40ed, 0x2bf3086e96c36c7b],
+    [0x9ff2ea486e59bb28, 0xd5e7e413f741ccf2, 0xa98cdb697c6cad5d, 0x12f16e2de6d4ad46],
+    [0xad919b0343b92d2f, 0x33f3d5d6ec6c4bf0, 0xa0262e3653ddd19f, 0x2a72147d230119f3],
+    [0x77df79acc10ba974, 0x0a2d9bcc26412e29, 0x6dc47f957806dc5f, 0x21be0e2c4bfd64e5],
+    [0x137fd4b6c21b444a, 0x71b84fb911aa57ae, 0x2749a3b54367b25a, 0x0e2d7e1dc946d70b],
+    [0x9f3f07d4b92b3e2e, 0xc31adb0eae3325dc, 0x6170a745d8a4188c, 0x2667f7fb5a4fa124],
+    [0x830e107da78e3405, 0x1550c12b08dfeb72, 0x30a783b66064697a, 0x2ccc6f431fb74007],
+    [0x2ec50621e38e6e5d, 0xae6dbee9e8ca0c24, 0x4f0201462420001f, 0x08888a94fc5a2ca3],
+    [0x6acfcd3ccbc53f2a, 0xa0d2fbe753af88b3, 0xad40dd42c9b6fdd7, 0x02977b34eeaa3cb6],
+    [0x10b5e9f971e16b9a, 0xbfcfe0d7e6ff8e96, 0xfd6fb6c9ea13a648, 0x120ccce13d28b75c],
+    [0xa0bf2af32f86ff3c, 0x92787a4575b2bd73, 0xc81e1b9770ea098c, 0x09fad2269c4a8e93],
+    [0xf630cffb60092d6f, 0x0debdb70775eeb8a, 0x0a4b310e4ac6f0fa, 0x026091fd3d4c44d5],
+    [0xb904fd2bca893994, 0x2543cc56afad6afc, 0xbb7fba9dfb6fc321, 0x29404aa2ba565b77],
+    [0xe7201351b7c883f9, 0xf1ffd86590e0827d, 0xd4e87c2548695b4e, 0x2749475c399aaf39],
+    [0xc7557dab65ffa222, 0xa2ebe2dc2e4da70a, 0x39912b50424685cb, 0x098c842322479f72],
+    [0x0191d0c053b51936, 0x758ace14c93c4da4, 0x31238e57fead7d5c, 0x18cef581222b647e],
+    [0x63b2aa681698de60, 0x3cbc0ca4a108f98d, 0xd4e746745e43711d, 0x13177839c68a5080],
+    [0xa907d88e5f0deffd, 0x26cc4df712c0e5f0, 0xc088f56f4b743256, 0x020ca696f531e43e],
+    [0x16e028387c7ac022, 0x93d13708c646841d, 0xfa805a30fc548db6, 0x27230eede9cccfc9],
+    [0x9057d2fe75bb281c, 0x6497c05969a01543, 0xd64fde34a342a178, 0x01645911c1198b01],
+    [0xfdca4a451a5d8643, 0x198971e14487056c, 0x6e439c88341ce25f, 0x2c323fe16481bf49],
+    [0x5af88ae6db5085c8, 0xb022c124d3bffe8b, 0x450bd2074c3e22e1, 0x0fc082dfe70728e8],
+    [0xfe8a488b423521ee, 0xbe9642116ac4c77e, 0xd8cdca568dcc25b3, 0x2052c174800db209],
+    [0x8dd9fd05b3ece9c0, 0x0be351ce8129065a, 0xaf96d621d5542319, 0x28e420e10df2fbb5],
+    [0x49d6e0bc3ae5069a, 0x55d6ae1bdacd1cb5, 0x9f783c4462a24db6, 0x25698ca5e24a1b79],
+    [0x34d9b857d6984d35, 0xa297b61074422ac1, 0xcf8ffbfa57d51049, 0x160a9981a5c89a57],
+    [0x64642b069997f3d4, 0x8b7cebf59ddbb0a0, 0x34d9b694b843f3bf, 0x21c91a39e145c3bc],
+    [0xd10975461e41734c, 0xd6eaa029d93f03b6, 0xd2b09345ef112345, 0x1ac8d80dcd5ee876],
+    [0xb8dbcea5657ce02c, 0x5d822895e2755544, 0x7c1662a4174c5222, 0x0ab3e6ad0ecf8b8e],
+    [0xc5b2237cbdab3377, 0xa52ef3ef5909b4e1, 0x27e3b0b917b3a21c, 0x1c675182512620ae],
+    [0x0c8a9092ac2bed45, 0xe37a4a3e07a7d75d, 0x3d948d0c85bad2e2, 0x2cdbc998dfd7affd],
+    [0x3e491a133bb63b2e, 0x24337350309dff83, 0x774bf67cc0dee333, 0x23b584a56e2117b0],
+    [0x2f3d20de1465e9a5, 0x0d133bc6ba4ec115, 0x8cb73030a3c9d2a1, 0x1e9e2b310f60ba9f],
+    [0x60bcd4f1fa5b22db, 0xc9ab5dab987520c4, 0xabc3e720140ae746, 0x0e01e365ba5b3031],
+    [0x1387d85bc1264e68, 0x43382011b61c9a4b, 0x7b7127340498d5c4, 0x040884cdcfc64bfc],
+    [0xa0a17de311ef9668, 0x353f1724d6067ed0, 0x0c74a3998f2bea36, 0x190b1ee1205eb950],
+    [0xfc28d1f832bd3b2c, 0xc1dfcf65ce61e165, 0xd04f52fc23cd9c08, 0x1647c72aec6c4388],
+    [0x842e595b71e4541d, 0x9f5494d89fb48b02, 0x799880cc4c873626, 0x2430006346a0145f],
+    [0x3221850252b757af, 0x9d853902bb16bacb, 0x365107a3da3ae7f6, 0x177b9a08343917e1],
+    [0x39b11d04666df4f8, 0x09cd53d0ae294234, 0x4e58862a68f5e326, 0x04a420e642b11ae9],
+    [0xcdfabeddb6a25c8f, 0xde2461858e956ccc, 0x105a88fab0afd810, 0x25d0e0f739fb39fc],
+    [0x2c4d4ffa0c740a27, 0x320cb15610eaed45, 0x85905cbf58651edc, 0x04476d91b7eff2fd],
+    [0xe9780fd9ca286fae, 0x1c28f6d5e1250cb5, 0x8bc9ca2419eb8dea, 0x1090c0b68b3d7d7b],
+    [0xbf2899cb5c6617be, 0x376f2d435855c10e, 0x448a725c5c7cd5ad, 0x25393ce3b9256d50],
+    [0x97d144f0fab46630, 0xed824388d6b93426, 0xfc862f306e6e5830, 0x25931c0c7371f4f1],
+    [0x03f73f22afbf62f6, 0x8a4d353185d58082, 0xc82aad51b0fb79cf, 0x2396cb501700bbe6],
+    [0xb3f0ce03b8724884, 0xa3dc9068c3cca7b5, 0x54ea748a7129a7b0, 0x26a363483348b589],
+    [0xa7a0e5b45fcd6897, 0xc99b893334215f6b, 0x8d6f1535b92c5478, 0x27ca107ca204f2a1],
+    [0x8d2d02b3b2d91056, 0xac15f7301178581d, 0xe4662bde326b2cce, 0x26da28fc097ed77c],
+    [0x72971ba56ee0cb89, 0x655774c1bb35d575, 0x703e3055070ac9cc, 0x056ab351691d8bb3],
+    [0x2d729af632376a90, 0xc22547a11ffc5015, 0xc76d109a2f481aa3, 0x2638b57f23b754ae],
+    [0xea7bc5519ede7cef, 0xc33e46a532bdec80, 0x32f492c2605184fd, 0x304754bb8c57d607],
+    [0x752ac93a9b7619ac, 0x48ec6857fc554010, 0x514f155b5806cbf7, 0x00d1727f8457ee03],
+    [0xc588e50947761fa3, 0xb5bca86805ec9419, 0x43ba295a303c72fa, 0x00ee1f3c66fbc05c],
+    [0x61f9297eb675d972, 0x10a19fbcfc59078c, 0x4a76b5a1d82415fd, 0x0afafadcf5b4dd4a],
+    [0xa8996d124dd04d0a, 0x65a234835a0a6a5e, 0x86ce45e8eed108ee, 0x0b2449f39746085e],
+    [0x6a7ddf4823dd5dd6, 0xf6c6f071ec3bdda7, 0xc9f37b0045227095, 0x206b0ce2f1b2c5b7],
+    [0x52c7e1029459409c, 0xcaffc3a4ef20fea8, 0xb696e67433628cd6, 0x0feba4fb87834c7c],
+    [0x8173e972336e55d3, 0x3b06f1315e6d70e1, 0x8926752e084e0251, 0x254dbfac74c49b0b],
+    [0x37719edfa0ca8762, 0x9606c5bd17910aeb, 0x4655168c367559e1, 0x0addb1372cee4e16],
+    [0x21a589d68831d759, 0x6ca4d8d81b1ae162, 0xc799024fb019f65c, 0x26b25b7e257f3e97],
+    [0x1960b1059e113ba3, 0xa4657b9ab00bdb5b, 0x413b8d4c658787e5, 0x090995b79acec240],
+    [0x2974178d44f73b7b, 0xeb0d8e40e99131f4, 0xc57299687843cea3, 0x08dbdc2e21ef11f2],
+    [0x58f170939785179f, 0x42fe9c491596ab67, 0x679faf752a0f78e3, 0x09e8aba671481197],
+    [0x7bc7c683f1363d5c, 0x7efd12a7f9eec94b, 0x659052a7ebaf816c, 0x1deb05180e833e45],
+    [0x35fd6523e5250879, 0x248997e8b2c24af3, 0xa926efbcc04aa9ee, 0x19a70ec6bdfc9098],
+    [0xeb93abe10bbf1f64, 0x66353a3777d8a3f1, 0x879986f9aab48905, 0x21d773660adafb8a],
+    [0xf6f46617841c3901, 0x97a6b01fcd667347, 0x3e20ba637b89d5d3, 0x09f1890f72e9dc71],
+    [0x08c2145c33b111c3, 0x48fa1f897bf219d6, 0x2a300c61e446998d, 0x05af459361eb454d],
+    [0xfa12fc850cc8b513, 0x5f336f15f340756c, 0x664a66dc75a65733, 0x0fa1a1d6829f0345],
+    [0x53655cf97a628bb0, 0xf4280fcf87f636f8, 0xbda0b1c0307ad543, 0x02e47a35bcc0c3a0],
+    [0x36621895204d0f12, 0x2034114601124910, 0xb8f90e78bf4c24b7, 0x14f773e9834c6bde],
+    [0xffb091962fc8f7cc, 0x642abe7cfd639992, 0x255cf19d29bc7d8e, 0x102d98cf502ed843],
+    [0xcba686a7e91373c2, 0x20d4c73ad3294738, 0x4c47f6c65da7ca23, 0x043dd5f4aa5a76dd],
+    [0x4cfdb2b26bd11efa, 0x6f0e7c79743a306f, 0xa6c0d29a48d4f267, 0x21833819c3337194],
+    [0xdc53262a3cff2b53, 0xeb4eca246c311eca, 0xb474a6819d116ca3, 0x0f281925cf5ee649],
+    [0x93ce662f15c238d6, 0xdf625dd60504d5dc, 0x44709c7746d6824e, 0x0d3e2477a7b10beb],
+    [0xacbfc3eff5aefc44, 0xd80f80ab51e73b49, 0x56ff8a01be9cde35, 0x2cd7f641bedbf669],
+    [0x86dd861310463cf8, 0x9149d24045811d7a, 0x4d09380f98b74e38, 0x29e95b492bf2f95f],
+    [0xf8b3c50df83cc13e, 0x9ae4c51af6ffeb57, 0x266efca86a6c810f, 0x22da66bc62e8f011],
+    [0x43072d841925554a, 0x220db79e8129df36, 0x3023491794f4aca3, 0x0fe6d30de7a82d16],
+    [0xc7e07ecb298fd67f, 0xd312d03fef1adfec, 0x123c46eff185c23a, 0x0050e842a1299909],
+    [0x885cbbdb63108c21, 0x666f9ddf714ed7c5, 0xbe34cc53a42d7733, 0x2130a3a7b3221222],
+    [0xe1d328e67b33c9fa, 0xaa66731f34a93280, 0xd8d5883fe0566c24, 0x2df9ee294edf99e3],
+    [0xa4b6fc5fc3372762, 0x58132396dc250aeb, 0xf26eb68cc21ff541, 0x1bf7d6e489ad8c0c],
+    [0x5d4778d311780e54, 0xcf7b807728bf7fe3, 0x61eaf739617ab136, 0x0c602fa155be9587],
+    [0xb63faf0121ed7f21, 0x5154080a24972fae, 0x32407d86b8d22d7d, 0x2e50e2c5b36aa205],
+    [0xb459123b180332e1, 0xf674995ee8409b42, 0x5710d6290ec4f782, 0x17c2510982a7b582],
+    [0xb2ab255f376b42a8, 0x21337b538eabd2f6, 0x6803ecf2465b885b, 0x0b0d52f03c8af727],
+    [0x61972dd8fab8bd14, 0xa9ac77c6c0f85d45, 0x5953d88a63f80647, 0x0f5633df1972b945],
+    [0x035498130a7f1572, 0x24780ff43e76e929, 0xe1422e9396811551, 0x0ebf7ad29ca13804],
+    [0x661b1103a720ffe2, 0xe18f94bee27c8a57, 0x0b02962173bba343, 0x1aff13c81bda47e8],
+    [0x5713be57efac6c07, 0xd3f31de1a3b58ff3, 0xda2465be85505862, 0x210449dbf5cf3061],
+    [0xb93ad1c3ee629817, 0x19d1e2f1d3001044, 0x7d75cd6d3c7b9dbe, 0x088230c2794e50c5],
+    [0x17a4d6dbb20e7e3a, 0x8cce9a9e16c77056, 0xda08dc464138dfc7, 0x1c408c256490b0a1],
+    [0x90dd6f557e9e3903, 0x8f7c77654d584404, 0x22d1771200fb0765, 0x074517e081eb4c1f],
+    [0xa05adf61e12fdcbf, 0xcee5530559d6cf0f, 0x88524bdb203691b4, 0x02d04e9c21df1dbd],
+    [0xa81db32cf1b67b13, 0xeb9b4650dae9f11a, 0x82e13ebd75de3b58, 0x2eb7a011b8bce910],
+    [0xe6030c18f0cf17b5, 0x4d2ac6bf95368304, 0x299f75d6e8a849b5, 0x2efda77ed35f4af0],
+    [0xc51b2440192ae064, 0x61a73d10852b8114, 0x2eddbeda65206d4f, 0x09199dcafd50ce64],
+    [0x76181cb4216e1562, 0xcb655d8c1797e9fa, 0x4dd319db666a75b5, 0x268c5cfc446d399c],
+    [0x012854eda11a18dc, 0x97b44e912cce6687, 0x26b0e9a36c805786, 0x2303a652c9490718],
+    [0x37073f4efb35fbdf, 0xbc5353eb110868d2, 0xc3f041f31dc45922, 0x27c53563b12a6ee2],
+    [0x2c278f22615d2b0e, 0x9969b5248cfe90f4, 0xf02bd82d0a510904, 0x1201a87eaf4ae618],
+    [0x6cb4b43dab2a443d, 0xcafcb1ba2c51e570, 0xad8214997bb069be, 0x2c43169439fcd69e],
+    [0x4fb9847e6304d944, 0x4f46cbb36d702e3c, 0xea03c45d6984c689, 0x0683597315359040],
+    [0x77795ad3a798d183, 0xb0425e158314197b, 0xafb93b128febd16f, 0x03545706706eab36],
+    [0xedb393b71a0c0e6b, 0x740bed23a6a37870, 0xd35f1fc051b31728, 0x1a33c254ec117619],
+    [0xf338e517f1690c78, 0x6e88f71e759b87e6, 0x67b0c002281caf99, 0x1ffe6968a4470cd5],
+    [0x107f4e02e355b393, 0x140ddd5d2a5c4483, 0xecb059c899fd80f4, 0x0fd66e03ba8808ff],
+    [0x7be3396b7fe013ab, 0x6c8617a7bdd5d74a, 0x197394552906b17e, 0x263ab69f13b966f8],
+    [0x6eb7e03e39ba4046, 0x3bd87d5aa3958fdd, 0x5054d5a165de413e, 0x16a425e47d111062],
+    [0xfcc5f73af9138d9a, 0x53cc31d13e39e909, 0xad752f03c673f0e2, 0x2dc510a4719ec10c],
+    [0xcb4aa709a94576e5, 0x423c5179329b7a82, 0x1bd1cad23d07dda3, 0x24df8e8d856c5b5e],
+    [0xbdf24a6cdce5620f, 0x7628249a01b09561, 0x3cd5c68915a042e8, 0x2bcc94ff4fc3c76f],
+    [0xb48be86852da97df, 0xd3295f52c38cffe6, 0x8de54e343df7c429, 0x076c1e88dc540c8d],
+    [0xa990fb8e12cb46e1, 0xe40ee1601120947d, 0x1c051fb12d9a5e4f, 0x09b5f209a451ac43],
+    [0x0683af75eb677c07, 0x64e9424f55b0f1ea, 0xaa88d6a44135a6ab, 0x205f17b0d8729e2e],
+    [0x0cdd1edf9f237029, 0x091681f0a4176172, 0x912638c38be046cd, 0x281c5c688836f6cf],
+    [0x4afa44f2592621f5, 0x009a44e7a02ea50e, 0x5f4d67448c471cf3, 0x1a053e6878e900f4],
+    [0x81742839d59e064c, 0x68efcb897e7bbee9, 0x07fb7ceac84e4f54, 0x100dc7d426debe30],
+    [0x0b1cb31b411e49c8, 0xb28905bdb62c82c8, 0xbb87e2cfadc8b75f, 0x17022672a016a957],
+    [0xe0c27203f954f4d2, 0x9fb8b547182b170d, 0x71053a87ebe15123, 0x1086db7e2760fc8b],
+    [0xb85dd24fd7584064, 0x2b41fb65a185536f, 0x02460ae4c2942fac, 0x15384fe39d73b633],
+    [0x519e551357709008, 0x47b1a853205fcfb5, 0x4bf4abc5342c6c74, 0x2ebb599fe9136d42],
+    [0xd996fac6f4d37288, 0x4cf278292b4ce3ee, 0xfec3c0f0542e4c5a, 0x1b4b5e87cfb9262c],
+    [0xb637febe659e5057, 0xbb4a7581bb4fba60, 0x801f3f82e302cafb, 0x2465053ae50b6885],
+    [0x6e0ab9d3d54859ef, 0xda9f05e18b3708bf, 0x095c5bb5d38f1b97, 0x114f32edcdea09cd],
+    [0xee28d8c2543c7148, 0x2e5e5519f3d18123, 0x6b387cd77be779ac, 0x2bc70dfeb2baab2f],
+    [0x12e49bfe32c05415, 0xa78348b9f6ec68a4, 0x775e3a61ad7e77b6, 0x01c9bf7a203ce22b],
+    [0x9f4ad00ccb57ee9b, 0x55cfb575ff6a97cd, 0x7bedb0295fbbcec3, 0x0514b0fe5909ea88],
+    [0x03e3202d7b6c1b7e, 0x2092520b12a201af, 0x1a132a8b058910a1, 0x267c76ec81934cc8],
+    [0xfb151cf757bde5d6, 0xf1622493ce83e95c, 0xc78c84babbb470ad, 0x29170e3322b3d8d5],
+    [0x36dd596ca41d9519, 0x5c6f09a45486cab5, 0xaf33e5d3873f9c33, 0x019f6a8124b19e33],
+    [0x5405d036242b60e9, 0x009ed8ea171518ae, 0x8b348e9db1981c27, 0x1904aa4d6908544a],
+    [0x3b932a476455ff1a, 0xb3cee1de9dd6f647, 0xf7f043956694e422, 0x26f17873949bc679],
+    [0xbdcac9b18bc48f75, 0x40c476031197131e, 0x193b33720b8aa540, 0x1ac668f612b8243c],
+    [0xa52f81568ef0663d, 0xccfbf8555be9796d, 0x196dae45bf624766, 0x0996d961a75c0d07],
+    [0x5425b395c24fc819, 0x74d171f99c63febb, 0xfd50d1b4383fbe66, 0x030c97e1b8cad1d4],
+    [0xee226f2d8bd0848f, 0xb3e523f1fe502642, 0x3953370255b68f89, 0x06e3ad6a46900e2d],
+    [0xa329890588cc916e, 0x65cb94b0e0455153, 0x6b6880e42f9880f5, 0x1d6b3755331cd021],
+    [0x3dd38c08bae531f2, 0xc35189dc0b85ac03, 0x59b041535e730ac8, 0x28e4dcba4b96f12a],
+    [0x7a3b3e4bc4a47a14, 0xb0712a476260376c, 0x8ccf484f2974b6a6, 0x08b6086046a83550],
+    [0x89643e15b9bb3b52, 0xb6fd85fba6a0536a, 0x444bcec97812019b, 0x162cd2ca7fe3b5f1],
+    [0x998c01c64d483a76, 0xb5c9a9c1192063d1, 0x05af5b11937e4f5c, 0x28f1e03baaea9bbc],
+    [0x6031a0bb6791ce10, 0x0127d2aab4aa7136, 0xa395af2734c25faa, 0x1bdb062778d7c15d],
+    [0x50329ebdd24749cb, 0x0fc98870b2324a8b, 0xcb2914e829627e0e, 0x2375839502e09890],
+    [0xe92d33611ed7bb50, 0x332dc87cfb2df456, 0xad7c55668dc9423a, 0x1fa8662fbcb61fb3],
+    [0x3f7f9736079d7694, 0x46e2fb2c47a5138f, 0xf8707f721716c8a4, 0x1e4fad2dd6b0a6f1],
+    [0x61b0f5f13731ffe7, 0xba3bd050059f53d2, 0x6df6f5fcdd1fa788, 0x211256d16c7269fd],
+    [0xf4a1079b12bcc5a5, 0xf42060e574dda341, 0x4f8e2a2e6af08318, 0x2e49084b336eceaa],
+    [0x758d25891fb00bb9, 0x1aecea08dfe14cab, 0x3bf35192ac680821, 0x0ce19f54cdc39f7f],
+    [0x84a8468bab2c14cb, 0x8dc60451e4ae4e1c, 0x3cc394221261d874, 0x0011c5d56c390e89],
+    [0x14a8cd8051579b4c, 0xca60e17bfa39b475, 0x8a9e05ee6af3dbb7, 0x17d79ff06b63ac2a],
+    [0xcab00173639015fa, 0xb1a8b35cd6416a2e, 0xdc74560093592b06, 0x19a7d3a446cb5393],
+    [0xaa6c3c97237037a6, 0x31f04596d8928da7, 0x2a808b2e1b9282f3, 0x030c00a0933dcdba],
+    [0x9370be8aab64139c, 0x935d2d00184c4acc, 0xae25ad080695382e, 0x16bcb447ce2d50f3],
+    [0x39255b7cd66feb1d, 0xe62124f37cab7b6d, 0x5ea4ec8715312997, 0x12341b46b0150aa2],
+    [0xdc89212db6a49ff4, 0x002fc28e296d1044, 0xb72a97b2bf610c84, 0x0e86d13917f44050],
+    [0xd059025b110c7262, 0x1052a181f8f2eb14, 0xd357e00b53d7f30d, 0x08e6eb4089d37d66],
+    [0xe10c48ce97ca7b18, 0x0415ccb351a1e0ce, 0x4738d15dd1481a0c, 0x2ea123856245f6c8],
+    [0x664db0f9c84dfa6f, 0x4195789025413abf, 0x3446e00330b16310, 0x2dca72b2ebcab8c2],
+    [0xc64a26cbd42b6a6b, 0xf2f6b4235f036fa4, 0x63329f585ec924b3, 0x06ff9ed50d327e84],
+    [0xc630a4535afbf730, 0xe2a60e0cca84ea2a, 0x47f7c9bda3d54df8, 0x246a10b7e3e00899],
+    [0x5c96b39d688b6691, 0x2f846a715ae67ad7, 0x18719ed99d700ee5, 0x22a63501c5f04b90],
+    [0x40ec07e514fae937, 0xb9164f58351d8aa1, 0x71799ac5d2e224cd, 0x2f4c50477f7fd9c6],
+    [0x0cda32cad851567e, 0x38f0ba8a4a23d4b5, 0x13b17f4d876d9a1e, 0x10ffb7aad1f51c7d],
+    [0x1a056935c35803ae, 0x784027352187e7af, 0xa4d39722532d5420, 0x0e9cefddc3c2d3be],
+    [0xa3e83bdd4ba62b41, 0xa1656f96a33c8978, 0xc23352e6dc6ea4af, 0x07af84a4d3141e7a],
+    [0xe940f3ec8a22c3c5, 0x1a39323d6e89b638, 0xf8de00d14b1e566d, 0x2d9e31a10aebc761],
+    [0x7656747be27e64c7, 0xd36034b314dad844, 0x33db1afd592f66f1, 0x27f19a6532e66b53],
+    [0x7e4d617d47d07ffd, 0x180ed99f8f3155cd, 0x54b2024c3b4a577a, 0x0058fa3c8454d633],
+    [0x708964956816a5d5, 0xb0414a205d3a175d, 0x67957c080699343e, 0x041627b6715b7809],
+    [0x89888f12b727c52d, 0xd98201471cf1f665, 0x7f632e57b958ccec, 0x006ac49dd9253edc],
+    [0xec46a6bf18301398, 0xec0c9c0d6d25a9a4, 0xb1d8c3616bbe3386, 0x0131adffd8bd7254],
+    [0xcf030e1cd8f9f5b6, 0x77977ad7e25e49a3, 0x4138e413ef62a283, 0x1c4a6f52c9fccf7a],
+    [0xd1f7958d2c2645f6, 0xfee048ae2078aeb7, 0x946551b3860ea479, 0x03f2a6be51ec677f],
+    [0x4d7376396b8ddc62, 0x8a1372d854311956, 0x391a0cb78ef3a964, 0x2da770aad2c2eb09],
+    [0x38062afb75d64a03, 0xc9d45fe866c359c7, 0xdc1802febfab02ce, 0x15278463665f74cd],
+    [0x8d580638ac54c773, 0x34e38ea966a08a6f, 0xc9731027090518d4, 0x12fe278aa36544ea],
+    [0x21b06ff30b6a23b6, 0xd8587604ca4f0d6e, 0x4c45d119d3f4cc7f, 0x149b9c802182558a],
+    [0x0adc4959b691dfe4, 0x55e044fd60dbac9a, 0x17d19319772f3c98, 0x0812e7b4d847bc85],
+    [0x2a66973703a0c61b, 0x555813c7e7503aea, 0xd8df7f28a0bfaa7f, 0x02ed8d8ddeafe3d9],
+    [0xa1304038662d4db8, 0x55e5e4d9a03d6b6b, 0x4deb6029f921029e, 0x0ebd073ba0537b51],
+    [0x44ee75b62eff9f59, 0x55b3e792c6afa08b, 0x05c6ba8d2ccd0282, 0x15c754d5b14b2c42],
+    [0xc29e7cbc30e8732a, 0x1cc1235270f4cbc5, 0xb0ed8fa6fa311b39, 0x169515c89ac5479d],
+    [0x6d9161f5cd9a4fef, 0x08bdc29f6ff03769, 0x2388f26210011016, 0x25479fbfb3a68f98],
+    [0xce09486e94be6071, 0xa7f8e6e972182196, 0x3c852cb0311a578c, 0x14475c4bd520451f],
+    [0x5c4e73363d097799, 0xd3a847502aec8d5f, 0xc9baf2798833a1df, 0x045a691066cc66be],
+    [0x013b8bcb37eba683, 0x147a8ca037221b90, 0xb833ac8a11e3a3f0, 0x26029c0c267c799f],
+    [0xb8101d5b948d1641, 0x73ce12a6a94a3e45, 0xf7c946969c1c2608, 0x163facb34ff572fb],
+    [0xaf8ee00c4240ee28, 0x13e06a6275e58688, 0x1d969320cc69d5ec, 0x2c714e96e1913b35],
+    [0xd52b804eff1d5fa6, 0xddd6268f06debfe2, 0x5aba84665ecd2bf9, 0x1c1661e2a7ce74b7],
+    [0xa8c3c068b7dc2c71, 0x6635b34c2a0889fe, 0x5e5af3e6619a47d2, 0x06a69ae795ee9bfe],
+    [0x31416c85d731d46a, 0x74dbdbadf54195c7, 0xc5d28b4c19a36093, 0x113d58535d892115],
+    [0x2ca1a74063b46101, 0xe534f1ff47f7917a, 0x38ff97d761da6042, 0x2ab89102e2b8d5e6],
+    [0x39804fd6a15ad1b3, 0x349031893da2b4fd, 0x962730c45e699546, 0x03c11ca79e41fdfe],
+    [0x6adf040faaf2669c, 0xf7f67b4d4cfe846c, 0x88014ddbbbfc9da1, 0x27096c6726214038],
+    [0xaa9dc2b57ef5be0d, 0x66db790ce486130c, 0xd504d4deeb53b13c, 0x2de32ad15497aef4],
+    [0x255754448eefd001, 0x28c738dddaec9f3d, 0xfd5d341310722a2d, 0x0dc108f2b0a280d2],
+    [0x7d34ca50365d832f, 0xbcdc3eebc409be7c, 0xc96858a1bb9efad5, 0x1869f3b763fe8164],
+    [0x8c6d33767129682d, 0xb616945e16a568d4, 0xf82559fe6a911843, 0x022ed3a2d9ff31cb],
+    [0x10c6aec077d026bc, 0x92fca1f27c19c266, 0x3944ed1365bd0e72, 0x2155d6005210169e],
+    [0x3ea5928c8caeae85, 0xec0c0556c91af3db, 0xcae93263f5f1b4bb, 0x0de1ba7a562a8f7a],
+    [0x6351dfa7da902563, 0x126f740bce8d637b, 0xcfce5bf46ec7da38, 0x05dbb4406024beab],
+    [0xc97e5427a368fd5e, 0x00e789e3fcd72dcc, 0xd4d8dc8ad778d32c, 0x05d4149baac413be],
+    [0x9212e22172c27b2e, 0xf0b6802fa941c787, 0x9be5046e7397e76f, 0x01cdf8b452d97c2b],
+    [0x1b0a22279d46c07c, 0xc4d7c5015eced8c7, 0xaf8085ff81adce33, 0x1fc6a71867027f56],
+    [0x568e0fda96aaafc2, 0xa9e185b75306d9c3, 0x5d4d59a5a7a3a42b, 0x1040bef4c642d034],
+    [0xa8081475ab8fad0d, 0x2b21e95676431918, 0xff2c91b289334a4d, 0x16b79c3a6bf316e0],
+    [0x3cbe1cdcd59f474e, 0xa34070e52b601fc1, 0x434b3a1387e3c8c6, 0x20dff1bc30f6db6b],
+    [0xdfb7fd8512ae060d, 0x062dd4171a726a8b, 0xc254955030a970f8, 0x0212ac2ab7a6eaae],
+    [0x13c8119f6996ae09, 0xb02dc03134f00442, 0x869a109c9215637c, 0x2f29377491474442],
+    [0x5837d9f32d814bfa, 0x73be9f3866aa284c, 0x25ec93c33fea6032, 0x0984ca6a5f9185d5],
+    [0xeb0ee9294b24f028, 0xe491361c8a6bd19c, 0x0d299bd6fa81220d, 0x0d080a6b6b3b6070],
+    [0xce929ed7c85a4544, 0x21acc85b6400264d, 0x6789530638cb0ad8, 0x0e65cd99e84b052f],
+    [0x55975da12736920b, 0xe30e3d20380ff6a6, 0x4808f72c716cd05e, 0x2e208875bc7ac122],
+    [0x4a60d1aa8592bad5, 0xae2e3b894afd29f6, 0x76a0b0ff3d7dfac1, 0x2989f3ae477c2fd3],
+    [0x3a74c3cea7189459, 0x918106a463290a3e, 0x9222d101e6fac0ce, 0x11361ce544e94137],
+    [0x34c8bd05a2061438, 0xd122a822b8fb366c, 0xa539e10c173f6a75, 0x1e8d014b86cb5a7d],
+    [0x212ee2c28ee98733, 0xa0c2324167ef6c91, 0x7ba812ad29558e23, 0x173f65adec8deee2],
+    [0x991bd695310eddd9, 0x5da5df7ad45499d0, 0xafee8bd0c779ac3e, 0x01c36daaf9f01f1b],
+    [0x4a6f5741f381e562, 0xf277d1a3f2fc8994, 0xaa9ab1c485bb85ff, 0x1353acb08c05adb4],
+    [0x20f1c87a3b064d34, 0x009d33deb4f93aeb, 0x1860e71ea1188ee4, 0x2e5abd2537207cad],
+    [0x421726ba8f69455c, 0x13deb4eb34913a13, 0xd02eedbb7ab85625, 0x191d5c5edaef42d3],
+    [0xee7939dd2dcd089e, 0x82a04c74c127de9d, 0x82a263fea6d7599d, 0x11d7f8d1f2692642],
+    [0x1bad085286cac971, 0x3445cb4cd6bc6f95, 0x90f79ad5e6799797, 0x04218fde366829ed],
+    [0x27b71c730d76d6dd, 0x027b73b489301c32, 0x048397ca5f47a202, 0x0070772f7cf52453],
+    [0xc1dc04dbe3d2b861, 0x67681a98cd051634, 0xc865b065687a1d9b, 0x038a389baef5d9a7],
+    [0xf19860789015a6e5, 0x0f39d00966a50bea, 0xcda446b2b4b59ccd, 0x09a5eefab8b36a80],
+    [0x9d16212c7584cd8c, 0x3d9ec99edfacb748, 0xb969c145109b4b58, 0x01b588848b8b47c8],
+    [0xfed91e30d42954a6, 0x545e5abfa323d817, 0x6e1af6dfc3341419, 0x0b846e4a390e560f],
+    [0x85cabfe85ce72fe3, 0xeb1513bc394fc4f9, 0x0d43a02ddb900040, 0x23a6679c7d9adb66],
+    [0xf72f31d6fe089254, 0x4c3402fb7c85eccc, 0x3e5caa35f1351e9f, 0x2e0374a699197e34],
+    [0x37d41913a7a27b48, 0x4b8cc0b1176bb9ec, 0x7f7a08af4cde3ff6, 0x0752cd899e52dc4d],
+    [0x99e2873dd7ae55a7, 0x0275142b664b802c, 0xc349a2b6d57397a5, 0x068f8813127299da],
+    [0xb374330f2da202b5, 0x86872d04a295b5b8, 0x677574167434b3f9, 0x2ba70a102355d549],
+    [0xb8609e70a0b50828, 0x09f9099b825dd289, 0xa334d1df03b55213, 0x2c467af88748abf6],
+    [0x96d29e5763e8f497, 0xe3a52c2d1a319572, 0x01009a2b448ae881, 0x05c5f20bef1bd827],
+    [0x3ac8c9fe61b73871, 0x0cd083a2c649d9f2, 0x842a381f6006e2c6, 0x0dc6385fdc567be5],
+    [0xd255baa8114b369c, 0x0378d5b84150d25e, 0x19d49911b8670fa7, 0x142d3983f3dc7f7e],
+    [0x00fa18c1b2df67bf, 0xf36f86a7a99aa35c, 0xfd7e6d98c96a0fa0, 0x29a01efb2f6aa894],
+    [0xd2a1af04eb613a76, 0x0ab9e8afc64555b7, 0x38c4a5066644ec63, 0x0525ffee737d6051],
+    [0xf177cf4238301dc8, 0x7e164f614910264e, 0xf076677ca0e82276, 0x1e807dca81d79581],
+    [0xdba56082dbd8757c, 0x4c0223e0f733a52f, 0x93510816472474d3, 0x0385fb3f89c74dc9],
+    [0xf558f337bab0ea01, 0xcfabd7016fd6ef1a, 0xe1a6298e53cae59f, 0x037640dc1afc0143],
+    [0xb7e4c0de896074b4, 0x5fd56cf031da8050, 0xf12a6c5260829eee, 0x1341999a1ed86919],
+    [0x7b244f65bed8ece7, 0x73afd642efdcc565, 0x56906d4bafb10ad7, 0x069eb075866b0af3],
+    [0xea1909a619033696, 0xd2726101d3afaa02, 0x95b38e8e08b3e646, 0x171c0b81e62136e3],
+    [0x5575107502acced8, 0xcbbdcb39bfe696f9, 0xb6eb55c311753e84, 0x2c81814c9453f51c],
+    [0xe39b8a86a7c3a604, 0xb04b39032adca92c, 0xe3b33fadcf274b2a, 0x29d843c0415d35d9],
+    [0xa7669cf3fae7728b, 0xd8e15ea5947f2cda, 0x8436bccdabb78750, 0x085d6a1070f3513d],
+    [0x9e2a5cda412fc394, 0xdbf1dd4e6706b02c, 0x10a44ba665bf302c, 0x11820363ed541daa],
+    [0x105840b751a16317, 0xdfd3150e05f1df5d, 0x02b60d61a83785bd, 0x201935a58f5c57fc],
+    [0xcfed610e87c02e9a, 0x46794eedd686cd8e, 0xe27a952abd33a03d, 0x0a8c2820c56971aa],
+    [0xd50dbc163a281877, 0x650b65ff33e6be1f, 0x04abd6d0bd7500b6, 0x180638ff301a64ca],
+    [0x3e9b321a812dd36b, 0xf85ca8a937cfbec6, 0x044f97114a4158a3, 0x095c716266f1de59],
+    [0x8d4087a497d73490, 0x83b618c5c1a68781, 0x20d86ffed6c7ca15, 0x17c31ea02fbc3783],
+    [0xe4d50a77f192a91b, 0x3c17a9764ccd660d, 0x6a7227e4192d149d, 0x05b86c4bb8ef318b],
+    [0xd6ce74ba986c7a7b, 0xab15f4a6ae0d237c, 0x6ff70d7ea2fde2c7, 0x265bc95df4a4c487],
+    [0xd6c886d4715c7929, 0x08701739c5f5b4b3, 0xd9bbe48f5fef2f69, 0x24752b47bc6c6bc8],
+    [0xe583b9324d974efe, 0x78d624b98da96ee5, 0xa0d86e527a964821, 0x14814a1e0f492a4e],
+    [0xc7523bca906f00bd, 0x997c8e041d3cfb3d, 0xbd60577378f29381, 0x10def931073b6479],
+    [0x1b4592c98610175f, 0xfa0b880d28c69d03, 0x7f706c0d8ab4ed03, 0x14f7ae770bf7e95f],
+    [0x75c5cc9dce1ce589, 0x9decc9d428ebe4e7, 0x9f926af40e8035d1, 0x1aef50a0cee751b5],
+    [0x951214b57e73cf5a, 0x086c2a2d57d09602, 0xba65ca60068dfe3b, 0x041935607172f68e],
+    [0x37a151d3bf452cb8, 0x0493fbefe83c8198, 0x573bd083959b856c, 0x26863e9dd24255d1],
+    [0x652738e63ff8b3af, 0xf5adf251ba62052c, 0xeb3d7a068bd087c9, 0x2036efb6f9830965],
+    [0xb00fa4f1b4f4ee9b, 0x30be4f75a753f854, 0x766b639a029969ca, 0x0c712a975b74dc9d],
+    [0x375731d387539699, 0x7afdff6456492ca3, 0x27afc99bfac1e680, 0x08014dab3cd1667e],
+    [0xb53009f79b34e6a4, 0x97c4dd4d37b4e8f3, 0xa82a4a79839d6a2b, 0x198d07192db4fac2],
+    [0xfd15784d1f63e572, 0x2709b29d53bb946d, 0xb23b4131426897a3, 0x29eb1de42a3ad381],
+];
+
+/// MDS matrix of circomlib `PoseidonEx` with `t = 5`, as canonical elements.
+#[rustfmt::skip]
+const MDS: [[[u64; 4]; WIDTH]; WIDTH] = [
+    [
+        [0x4fc6fdbcf38d7da1, 0x9f22e57ace3cd7f6, 0x080b0af133b9e436, 0x251e7fdf99591080],
+        [0x48550486e91c7765, 0xfe26ea9ca238d6e3, 0x47cbd3b1c17d97c7, 0x25fb50b65acf4fb0],
+        [0x6ea1e31ed5767833, 0x6deb5325f367a455, 0x355f39ebf62f91b0, 0x293d617d7da72102],
+        [0x6145f14ba6d3c1c4, 0x66599e575a9b7edf, 0x960111ac25da4743, 0x104d0295ab00c85e],
+        [0x3ed5b90f2f69c977, 0x792b3813954fe9bf, 0x7dea3e336cd96a39, 0x0aaa35e2c84baf11],
+    ],
+    [
+        [0x69f661b2eb74c839, 0x052903dc6609ea69, 0xc03e17c1d1dcdb02, 0x2a70b9f1d4bbccdb],
+        [0x115b8e2e991ccd7a, 0xba9f92ad8ef4b979, 0x6315a9934f1b8a1b, 0x281154651c921e74],
+        [0xb86a894f7db36c37, 0x8ccd8fdb9ee2b45f, 0x0b53c732134efa33, 0x28c2be2f8264f95f],
+        [0xc4ec8fa75e530a13, 0x9b626d8cb4dc18dc, 0x6d427c890b1883bb, 0x21888041e6febd54],
+        [0x350d65e2cbff4941, 0xe810930e3ea4574a, 0x80195b9592d8cf2b, 0x14ddb5fada0171db],
+    ],
+    [
+        [0xa4fa8478970d401d, 0x55b91bff652ad69a, 0xdea43265306a37ed, 0x2f69a7198e1fbcc7],
+        [0x6d6a3747594d3052, 0x67ba312b34140e71, 0xad931ab80e37bbb2, 0x001c1edd62645b73],
+        [0xa7f9a4b228bfc32b, 0x39c40c603049466f, 0xce2f2c96c69663c4, 0x15b98ce93e47bc64],
+        [0x65051de33163cf9c, 0xcba8458b28e44d92, 0x58f65be2fbac809f, 0x12c7e2adfa524e59],
+        [0x531ec2de53bbd167, 0xaf67ce79816ef468, 0x49018222e7b8922e, 0x2efc2b90d6881348],
+    ],
+    [
+        [0xf1afd6c5f721f830, 0x3c3ffa4550bd2514, 0x1981e55e3e1a29a1, 0x0c3f050a6bf5af15],
+        [0xeda7784320a1a36e, 0x8b2efe2ecd424a73, 0xfa75ba7992bd34f0, 0x0dec54e6dbf75205],
+        [0x7cc75cf32d8136fa, 0x98364a11f4d988fb, 0x20225815034b1960, 0x1c482a25a729f5df],
+        [0xb8b607ae9fd8514a, 0x0812ac2fc9a14a5f, 0x52732624e4ab9436, 0x2625ce48a7b39a42],
+        [0x9fb9f28af710251f, 0xed7ef8e300b9a8bb, 0x86f7cd4fd710c509, 0x07f017a7ebd56dd0],
+    ],
+    [
+        [0xe2f78c2ccc2e3595, 0x7c5e55c20146259b, 0xf97c9d6186c6c3ea, 0x2a20e3a4a0e57d92],
+        [0x403b01feb727a549, 0x701673aed820d9c4, 0xaafb1e9a5d63c0ee, 0x1049f8210566b51f],
+        [0x61b9299b82d69c8e, 0xef357a69e3e86b55, 0x68002bd9d1b96b4b, 0x02ecac687ef5b4b5],
+        [0xd4ebcf11bbe1e37b, 0xbdcb6b58ba40441e, 0x6808f88c9ba903d3, 0x2d3a1aea2e6d4446],
+        [0xc98d803bfed65e64, 0xb39c4a7a72dbb6d9, 0xc9ad171e4f35fe49, 0x14074bb14c982c81],
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::poseidon_bn128::{
+        goldilocks_limbs, permute, permute_many, poseidon, Fr, GOLDILOCKS_ORDER, MODULUS, WIDTH,
+    };
+
+    #[test]
+    fn test_fr_arithmetic() {
+        let one = Fr::from_canonical([1, 0, 0, 0]);
+        let minus_one = Fr::from_canonical([MODULUS[0] - 1, MODULUS[1], MODULUS[2], MODULUS[3]]);
+        assert_eq!(one.add(minus_one), Fr::ZERO);
+        assert_eq!(minus_one.mul(minus_one), one);
+        assert_eq!(
+            Fr::from_canonical([3, 0, 0, 0]).pow5().to_canonical(),
+            [243, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_permute() {
+        // The same vector as `test_poseidon_bn128`, which absorbs it with a single permutation.
+        let mut input = [0; 12];
+        input[..5].copy_from_slice(&[
+            8917524657281059100,
+            13029010200779371910,
+            16138660518493481604,
+            17277322750214136960,
+            1441151880423231822,
+        ]);
+        let output = permute(input);
+        assert_eq!(
+            output[..4],
+            [
+                16736853722845225729,
+                1446699130810517790,
+                15445626857806971868,
+                6331160477881736675
+            ]
+        );
+    }
+
+    const MINUS_ONE: [u64; 4] = [MODULUS[0] - 1, MODULUS[1], MODULUS[2], MODULUS[3]];
+
+    /// Initial state followed by the inputs of circomlib's `PoseidonEx(4, 4)`, and its four
+    /// outputs. The first output for the inputs 1, 2, 3, 4 is `poseidon([1, 2, 3, 4])` of
+    /// circomlibjs, the others come from a big-integer reference with the same constants.
+    #[rustfmt::skip]
+    const POSEIDON_EX_VECTORS: [([[u64; 4]; WIDTH], [[u64; 4]; 4]); 3] = [
+        (
+            [[0; 4], [1, 0, 0, 0], [2, 0, 0, 0], [3, 0, 0, 0], [4, 0, 0, 0]],
+            [
+                [0xbaa525df65250465, 0x37e60ebb1ce0663d, 0x9dcefa40e4510b98, 0x299c867db6c1fdd7],
+                [0xd86206154ffe053d, 0xd8bb2b429aceac47, 0xb27dafd89bb98862, 0x1148aaef609aa338],
+                [0xc6b0637d34f24907, 0x4044c38ee1672c1a, 0x23f6665ff9a0111f, 0x24febb87fed7462e],
+                [0x8e5d9cf6e78b3d3e, 0x0707059576406b24, 0x81c186beaf611006, 0x0eb08f6d809668a9],
+            ],
+        ),
+        (
+            [[0; 4]; WIDTH],
+            [
+                [0x88c1206db73e9946, 0x0937921b8b790604, 0x51209694d9c21525, 0x0532fd436e19c70e],
+                [0xea0fa9adfeb75e02, 0x3ddc6724a8e6133e, 0x2ddd736e15a21a65, 0x2875620e99eb8e79],
+                [0x20937e397adc9749, 0x8c751e2128e0b52e, 0x4e1d7e6abde7105f, 0x13447082a37b44ef],
+                [0xd371a70c8451fef1, 0xbb1f8495d1ac5269, 0x5b6ad933f828e54e, 0x25d20a49f75ad4c0],
+            ],
+        ),
+        (
+            [MINUS_ONE; WIDTH],
+            [
+                [0xac5da483b05a5b2b, 0xdd2ef4d564fc5d00, 0x946029cc0c41eaf5, 0x115ba2b82e6b8a3a],
+                [0x5e81441ca052e067, 0x4e6e39d5200dddb4, 0x31700aacb6dde715, 0x08d3ed496cd80060],
+                [0x49e0f5895efbd818, 0x5303c965fb6ef3d8, 0xc906976c8caeabe7, 0x2170827581da95ca],
+                [0xc17e125401d282e4, 0x8cfd8ec6950f2899, 0xb61e63db9b1c5fc5, 0x1a9e0f82ca45df50],
+            ],
+        ),
+    ];
+
+    #[test]
+    fn test_poseidon_ex() {
+        for (input, expected) in POSEIDON_EX_VECTORS {
+            let [output] = poseidon([input.map(Fr::from_canonical)]);
+            let output: [[u64; 4]; 4] = core::array::from_fn(|i| output[i].to_canonical());
+            assert_eq!(output, expected, "{:?}", input);
+        }
+    }
+
+    /// `Poseidon_BN` on a final state element whose `Num2Bits_strict` decomposition is claimed
+    /// to be the 254-bit value `x`. `None` if `x` is not the canonical value, which
+    /// `Num2Bits_strict` rejects with `AliasCheck`.
+    fn circom_goldilocks_limbs(x: [u64; 4]) -> Option<[u64; 3]> {
+        let reversed = |x: [u64; 4]| [x[3], x[2], x[1], x[0]];
+        if reversed(x) >= reversed(MODULUS) {
+            return None;
+        }
+        let bits = (0..254)
+            .map(|k| (x[k / 64] >> (k % 64)) & 1)
+            .collect::<Vec<_>>();
+        Some([0, 1, 2].map(|j| {
+            let limb_bits = &bits[(2 - j) * 64..(3 - j) * 64];
+            let limb = limb_bits
+                .iter()
+                .enumerate()
+                .fold(0u64, |acc, (k, &b)| acc | (b << k));
+            let hi_ones = limb_bits[32..].iter().all(|&b| b == 1);
+            let lo_zero = limb_bits[..32].iter().all(|&b| b == 0);
+            let ge = (hi_ones && !lo_zero) as u64;
+            limb.wrapping_sub(ge * GOLDILOCKS_ORDER)
+        }))
+    }
+
+    /// `x + MODULUS`, if it still fits in the 254 bits of `Num2Bits`.
+    fn alias(x: [u64; 4]) -> Option<[u64; 4]> {
+        let mut sum = [0; 4];
+        let mut carry = false;
+        for k in 0..4 {
+            let (s, c1) = x[k].overflowing_add(MODULUS[k]);
+            let (s, c2) = s.overflowing_add(carry as u64);
+            sum[k] = s;
+            carry = c1 || c2;
+        }
+        (!carry && sum[3] >> 62 == 0).then_some(sum)
+    }
+
+    #[test]
+    fn test_goldilocks_limbs() {
+        let edge_limbs = [
+            0,
+            1,
+            (1 << 32) - 1,
+            1 << 32,
+            GOLDILOCKS_ORDER - 1,
+            GOLDILOCKS_ORDER,
+            GOLDILOCKS_ORDER + 1,
+            u64::MAX - 1,
+            u64::MAX,
+        ];
+        let mut rng = 0x9e3779b97f4a7c15u64;
+        let mut next = || {
+            rng ^= rng << 13;
+            rng ^= rng >> 7;
+            rng ^= rng << 17;
+            rng
+        };
+
+        let mut values = vec![];
+        for &a in &edge_limbs {
+            for &b in &edge_limbs {
+                values.push([a, b, next(), next() % MODULUS[3]]);
+                values.push([next(), a, b, next() % MODULUS[3]]);
+            }
+        }
+        for _ in 0..10_000 {
+            values.push([next(), next(), next(), next() % MODULUS[3]]);
+        }
+        values.push([MODULUS[0] - 1, MODULUS[1], MODULUS[2], MODULUS[3]]);
+
+        let mut num_aliases = 0;
+        for x in values {
+            let limbs = goldilocks_limbs(x);
+            assert_eq!(Some(limbs), circom_goldilocks_limbs(x), "{:?}", x);
+            // The other decomposition of the same element, which Num2Bits(254) would accept.
+            if let Some(alias) = alias(x) {
+                assert_eq!(circom_goldilocks_limbs(alias), None, "{:?}", alias);
+                num_aliases += 1;
+            }
+            assert!(limbs.iter().all(|&l| l < GOLDILOCKS_ORDER));
+            // Also the value GlReduce constrains.
+            assert_eq!(
+                limbs,
+                [x[2], x[1], x[0]].map(|l| l % GOLDILOCKS_ORDER),
+                "{:?}",
+                x
+            );
+            // The split commutes with the Montgomery form.
+            assert_eq!(Fr::from_canonical(x).to_canonical(), x);
+        }
+        assert!(num_aliases > 1_000);
+    }
+
+    #[test]
+    fn test_permute_many() {
+        // Seven states cover both a batch of four and the remainder.
+        let mut states = vec![[0; 12]];
+        for i in 1..7 {
+            let mut state = permute(states[i - 1]);
+            state[0] = i as u64;
+            states.push(state);
+        }
+        let expected = states.iter().map(|&s| permute(s)).collect::<Vec<_>>();
+        permute_many(&mut states);
+        assert_eq!(states, expected);
+    }
+}
@@ -0,0 +1,55 @@
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::Field;
+use plonky2::fri::FriConfig;
+use plonky2::hash::hash_types::RichField;
+
+/// Conjectured bits of security of a FRI based plonky2 proof.
+///
+/// Each query round contributes `rate_bits` bits and grinding adds `proof_of_work_bits`, under
+/// the usual FRI conjecture. Challenges are sampled from the extension field, so the result can
+/// never exceed its size, `extension_degree * field_bits`.
+pub fn conjectured_security_bits(
+    rate_bits: usize,
+    num_query_rounds: usize,
+    proof_of_work_bits: u32,
+    extension_degree: usize,
+    field_bits: usize,
+) -> usize {
+    let fri_bits = rate_bits * num_query_rounds + proof_of_work_bits as usize;
+    fri_bits.min(extension_degree * field_bits)
+}
+
+/// Conjectured bits of security of proofs generated with `fri_config` over `F^D`.
+pub fn fri_security_bits<F: RichField + Extendable<D>, const D: usize>(
+    fri_config: &FriConfig,
+) -> usize {
+    conjectured_security_bits(
+        fri_config.rate_bits,
+        fri_config.num_query_rounds,
+        fri_config.proof_of_work_bits,
+        D,
+        F::BITS,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+
+    use crate::security::{conjectured_security_bits, fri_security_bits};
+
+    #[test]
+    fn test_security_bits() {
+        let standard_config = CircuitConfig::standard_recursion_config();
+        assert_eq!(
+            fri_security_bits::<GoldilocksField, 2>(&standard_config.fri_config),
+            100
+        );
+        // The final config of test_verifier_without_public_inputs.
+        assert_eq!(conjectured_security_bits(8, 10, 20, 2, 64), 100);
+        // Capped by the size of the quadratic extension.
+        assert_eq!(conjectured_security_bits(8, 20, 20, 2, 64), 128);
+        assert_eq!(conjectured_security_bits(1, 28, 16, 2, 64), 44);
+    }
+}
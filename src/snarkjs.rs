@@ -0,0 +1,509 @@
+//! Readers and writers for the binary files of the circom/snarkjs toolchain: `.r1cs` constraint
+//! systems, `.wtns` witnesses and `.sym` signal names.
+//!
+//! Both binary formats are sequences of typed sections after a 4 byte magic and a version. Field
+//! elements are stored little-endian in `n8` bytes, in canonical form.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Read, Write};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use num::{BigUint, Zero};
+
+/// Sections of a binary file, by type.
+pub(crate) struct BinFile {
+    sections: HashMap<u32, Vec<u8>>,
+}
+
+impl BinFile {
+    pub(crate) fn read(mut r: impl Read, magic: &[u8; 4], max_version: u32) -> Result<Self> {
+        let mut bytes = vec![];
+        r.read_to_end(&mut bytes)?;
+        let mut reader = Reader(&bytes);
+        ensure!(
+            reader.bytes(4)? == magic,
+            "not a .{} file",
+            String::from_utf8_lossy(magic)
+        );
+        let version = reader.u32()?;
+        ensure!(version <= max_version, "unsupported version {}", version);
+
+        let mut sections = HashMap::new();
+        for _ in 0..reader.u32()? {
+            let section_type = reader.u32()?;
+            let size = reader.u64()? as usize;
+            let content = reader.bytes(size)?.to_vec();
+            ensure!(
+                sections.insert(section_type, content).is_none(),
+                "duplicate section {}",
+                section_type
+            );
+        }
+        Ok(Self { sections })
+    }
+
+    pub(crate) fn section(&self, section_type: u32) -> Result<Reader<'_>> {
+        self.sections
+            .get(&section_type)
+            .map(|s| Reader(s))
+            .ok_or_else(|| anyhow!("missing section {}", section_type))
+    }
+
+    fn write(mut w: impl Write, magic: &[u8; 4], version: u32, sections: &[Vec<u8>]) -> Result<()> {
+        w.write_all(magic)?;
+        w.write_all(&version.to_le_bytes())?;
+        w.write_all(&(sections.len() as u32).to_le_bytes())?;
+        for (i, content) in sections.iter().enumerate() {
+            w.write_all(&(i as u32 + 1).to_le_bytes())?;
+            w.write_all(&(content.len() as u64).to_le_bytes())?;
+            w.write_all(content)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    pub(crate) fn bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        ensure!(self.0.len() >= n, "unexpected end of file");
+        let (bytes, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn field(&mut self, n8: usize) -> Result<BigUint> {
+        Ok(BigUint::from_bytes_le(self.bytes(n8)?))
+    }
+}
+
+/// Byte size of the field elements stored for `prime`.
+fn field_size(prime: &BigUint) -> usize {
+    (prime.bits().div_ceil(64) * 8) as usize
+}
+
+fn push_field(buf: &mut Vec<u8>, x: &BigUint, n8: usize) {
+    let mut bytes = x.to_bytes_le();
+    bytes.resize(n8, 0);
+    buf.extend_from_slice(&bytes);
+}
+
+/// A witness, all the signal values of a circuit indexed by wire.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Wtns {
+    pub prime: BigUint,
+    pub witness: Vec<BigUint>,
+}
+
+impl Wtns {
+    pub fn read(r: impl Read) -> Result<Self> {
+        let file = BinFile::read(r, b"wtns", 2)?;
+        let mut header = file.section(1)?;
+        let n8 = header.u32()? as usize;
+        let prime = header.field(n8)?;
+        let num_witness = header.u32()? as usize;
+
+        let mut values = file.section(2)?;
+        let witness = (0..num_witness)
+            .map(|_| values.field(n8))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { prime, witness })
+    }
+
+    /// Writes the witness in the `.wtns` format, version 2.
+    pub fn write(&self, w: impl Write) -> Result<()> {
+        let n8 = field_size(&self.prime);
+        let mut header = vec![];
+        header.extend_from_slice(&(n8 as u32).to_le_bytes());
+        push_field(&mut header, &self.prime, n8);
+        header.extend_from_slice(&(self.witness.len() as u32).to_le_bytes());
+
+        let mut values = vec![];
+        for x in &self.witness {
+            ensure!(x < &self.prime, "witness value {} is not reduced", x);
+            push_field(&mut values, x, n8);
+        }
+        BinFile::write(w, b"wtns", 2, &[header, values])
+    }
+}
+
+/// Sparse linear combination of wires, as `(wire, coefficient)` pairs.
+pub type LinearCombination = Vec<(u32, BigUint)>;
+
+/// The constraint `a * b = c` on the witness.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Constraint {
+    pub a: LinearCombination,
+    pub b: LinearCombination,
+    pub c: LinearCombination,
+}
+
+/// A rank-1 constraint system. Wire 0 is the constant 1, followed by the public outputs, the
+/// public inputs and the private inputs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct R1cs {
+    pub prime: BigUint,
+    pub num_wires: u32,
+    pub num_pub_out: u32,
+    pub num_pub_in: u32,
+    pub num_prv_in: u32,
+    pub num_labels: u64,
+    pub constraints: Vec<Constraint>,
+    /// Label, or `.sym` signal index, of each wire.
+    pub wire_to_label: Vec<u64>,
+}
+
+impl R1cs {
+    /// Reads an `.r1cs` file. Custom gate sections, only used by PLONK backends, are ignored.
+    pub fn read(r: impl Read) -> Result<Self> {
+        let file = BinFile::read(r, b"r1cs", 1)?;
+        let mut header = file.section(1)?;
+        let n8 = header.u32()? as usize;
+        let prime = header.field(n8)?;
+        let num_wires = header.u32()?;
+        let num_pub_out = header.u32()?;
+        let num_pub_in = header.u32()?;
+        let num_prv_in = header.u32()?;
+        let num_labels = header.u64()?;
+        let num_constraints = header.u32()?;
+
+        let mut section = file.section(2)?;
+        let mut lc = || -> Result<LinearCombination> {
+            (0..section.u32()?)
+                .map(|_| Ok((section.u32()?, section.field(n8)?)))
+                .collect()
+        };
+        let constraints = (0..num_constraints)
+            .map(|_| {
+                Ok(Constraint {
+                    a: lc()?,
+                    b: lc()?,
+                    c: lc()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut section = file.section(3)?;
+        let wire_to_label = (0..num_wires)
+            .map(|_| section.u64())
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            prime,
+            num_wires,
+            num_pub_out,
+            num_pub_in,
+            num_prv_in,
+            num_labels,
+            constraints,
+            wire_to_label,
+        })
+    }
+
+    pub fn write(&self, w: impl Write) -> Result<()> {
+        let n8 = field_size(&self.prime);
+        let mut header = vec![];
+        header.extend_from_slice(&(n8 as u32).to_le_bytes());
+        push_field(&mut header, &self.prime, n8);
+        for x in [
+            self.num_wires,
+            self.num_pub_out,
+            self.num_pub_in,
+            self.num_prv_in,
+        ] {
+            header.extend_from_slice(&x.to_le_bytes());
+        }
+        header.extend_from_slice(&self.num_labels.to_le_bytes());
+        header.extend_from_slice(&(self.constraints.len() as u32).to_le_bytes());
+
+        let mut constraints = vec![];
+        for constraint in &self.constraints {
+            for lc in [&constraint.a, &constraint.b, &constraint.c] {
+                constraints.extend_from_slice(&(lc.len() as u32).to_le_bytes());
+                for (wire, coeff) in lc {
+                    constraints.extend_from_slice(&wire.to_le_bytes());
+                    push_field(&mut constraints, coeff, n8);
+                }
+            }
+        }
+
+        let mut wire_to_label = vec![];
+        for label in &self.wire_to_label {
+            wire_to_label.extend_from_slice(&label.to_le_bytes());
+        }
+        BinFile::write(w, b"r1cs", 1, &[header, constraints, wire_to_label])
+    }
+
+    /// Evaluates a linear combination on the witness.
+    pub fn evaluate(&self, lc: &LinearCombination, witness: &[BigUint]) -> BigUint {
+        lc.iter().fold(BigUint::zero(), |acc, (wire, coeff)| {
+            acc + coeff * &witness[*wire as usize]
+        }) % &self.prime
+    }
+
+    pub fn is_satisfied(&self, constraint: &Constraint, witness: &[BigUint]) -> bool {
+        let a = self.evaluate(&constraint.a, witness);
+        let b = self.evaluate(&constraint.b, witness);
+        let c = self.evaluate(&constraint.c, witness);
+        a * b % &self.prime == c
+    }
+
+    /// Index of the first constraint the witness does not satisfy.
+    pub fn first_unsatisfied(&self, witness: &[BigUint]) -> Option<usize> {
+        self.unsatisfied(witness).next()
+    }
+
+    /// Indices of all the constraints the witness does not satisfy.
+    pub fn unsatisfied<'a>(&'a self, witness: &'a [BigUint]) -> impl Iterator<Item = usize> + 'a {
+        self.constraints
+            .iter()
+            .enumerate()
+            .filter(|(_, constraint)| !self.is_satisfied(constraint, witness))
+            .map(|(i, _)| i)
+    }
+
+    /// Formats a linear combination with signal names, printing coefficients above `prime / 2`
+    /// as negative numbers.
+    fn format_lc(&self, lc: &LinearCombination, symbols: &Symbols) -> String {
+        if lc.is_empty() {
+            return "0".to_string();
+        }
+        let half = &self.prime / 2u32;
+        let terms = lc
+            .iter()
+            .map(|(wire, coeff)| {
+                let (sign, abs) = if coeff > &half {
+                    ("-", &self.prime - coeff)
+                } else {
+                    ("", coeff.clone())
+                };
+                match (*wire, abs == BigUint::from(1u32)) {
+                    (0, _) => format!("{}{}", sign, abs),
+                    (_, true) => format!("{}{}", sign, symbols.display(*wire)),
+                    (_, false) => format!("{}{}*{}", sign, abs, symbols.display(*wire)),
+                }
+            })
+            .collect::<Vec<_>>();
+        terms.join(" + ")
+    }
+
+    /// Describes a constraint for diagnostics: `(A) * (B) = (C)` with signal names, the value of
+    /// each side, then the value of every signal involved.
+    pub fn describe(&self, index: usize, witness: &[BigUint], symbols: &Symbols) -> String {
+        let constraint = &self.constraints[index];
+        let a = self.evaluate(&constraint.a, witness);
+        let b = self.evaluate(&constraint.b, witness);
+        let c = self.evaluate(&constraint.c, witness);
+        let mut out = format!(
+            "constraint {}: ({}) * ({}) = ({})\n  {} * {} = {}, expected {}\n",
+            index,
+            self.format_lc(&constraint.a, symbols),
+            self.format_lc(&constraint.b, symbols),
+            self.format_lc(&constraint.c, symbols),
+            a,
+            b,
+            &a * &b % &self.prime,
+            c,
+        );
+        let mut wires = vec![];
+        for (wire, _) in constraint
+            .a
+            .iter()
+            .chain(&constraint.b)
+            .chain(&constraint.c)
+        {
+            if *wire != 0 && !wires.contains(wire) {
+                wires.push(*wire);
+            }
+        }
+        for wire in wires {
+            out += &format!("  {} = {}\n", symbols.display(wire), witness[wire as usize]);
+        }
+        out
+    }
+
+    /// Checks `wtns` against the constraints, naming the signals of the first unsatisfied one.
+    pub fn check(&self, wtns: &Wtns, symbols: &Symbols) -> Result<()> {
+        ensure!(
+            wtns.prime == self.prime,
+            "witness and constraints use different fields"
+        );
+        ensure!(
+            wtns.witness.len() == self.num_wires as usize,
+            "witness has {} values, expected {}",
+            wtns.witness.len(),
+            self.num_wires
+        );
+        if let Some(i) = self.first_unsatisfied(&wtns.witness) {
+            let constraint = &self.constraints[i];
+            let mut names = vec![];
+            for (wire, _) in constraint
+                .a
+                .iter()
+                .chain(&constraint.b)
+                .chain(&constraint.c)
+            {
+                let name = symbols.name(*wire).unwrap_or("?");
+                if *wire != 0 && !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            bail!("constraint {} is not satisfied: {}", i, names.join(", "));
+        }
+        Ok(())
+    }
+}
+
+/// Signal names from a `.sym` file, whose lines are `label,wire,component,name`. Signals removed
+/// by the optimizer have wire `-1`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Symbols {
+    names: HashMap<u32, String>,
+}
+
+impl Symbols {
+    pub fn read(r: impl BufRead) -> Result<Self> {
+        let mut names = HashMap::new();
+        for line in r.lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let fields = line.splitn(4, ',').collect::<Vec<_>>();
+            ensure!(fields.len() == 4, "invalid .sym line {:?}", line);
+            let wire: i64 = fields[1].parse()?;
+            if wire >= 0 {
+                names
+                    .entry(wire as u32)
+                    .or_insert_with(|| fields[3].to_string());
+            }
+        }
+        Ok(Self { names })
+    }
+
+    /// The name of the first signal assigned to `wire`.
+    pub fn name(&self, wire: u32) -> Option<&str> {
+        self.names.get(&wire).map(|s| s.as_str())
+    }
+
+    /// The name of `wire`, or `wire<index>` for wires without one.
+    pub fn display(&self, wire: u32) -> String {
+        self.name(wire)
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| format!("wire{}", wire))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use crate::snarkjs::{Constraint, R1cs, Symbols, Wtns};
+
+    /// `out <== x * y` with public `out` and private `x`, `y`, over the Goldilocks field.
+    fn mul_circuit() -> (R1cs, Symbols) {
+        let prime = BigUint::from(0xffffffff00000001u64);
+        let r1cs = R1cs {
+            num_wires: 4,
+            num_pub_out: 1,
+            num_pub_in: 0,
+            num_prv_in: 2,
+            num_labels: 4,
+            constraints: vec![Constraint {
+                a: vec![(2, prime.clone() - 1u32)],
+                b: vec![(3, 1u32.into())],
+                c: vec![(1, prime.clone() - 1u32)],
+            }],
+            wire_to_label: vec![0, 1, 2, 3],
+            prime,
+        };
+        let symbols = Symbols::read("1,1,0,main.out\n2,2,0,main.x\n3,3,0,main.y\n".as_bytes());
+        (r1cs, symbols.unwrap())
+    }
+
+    #[test]
+    fn test_r1cs_round_trip() {
+        let (r1cs, _) = mul_circuit();
+        let mut bytes = vec![];
+        r1cs.write(&mut bytes).unwrap();
+        assert_eq!(&bytes[..4], b"r1cs");
+        assert_eq!(R1cs::read(&bytes[..]).unwrap(), r1cs);
+        assert!(R1cs::read(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_wtns_round_trip() {
+        let prime = BigUint::from(0xffffffff00000001u64);
+        let wtns = Wtns {
+            witness: [1u64, 2, 0xffffffff00000000].map(BigUint::from).to_vec(),
+            prime: prime.clone(),
+        };
+        let mut bytes = vec![];
+        wtns.write(&mut bytes).unwrap();
+
+        assert_eq!(&bytes[..4], b"wtns");
+        assert_eq!(bytes.len(), 12 + (12 + 16) + (12 + 3 * 8));
+        assert_eq!(&bytes[24..28], &8u32.to_le_bytes());
+        assert_eq!(&bytes[28..36], &0xffffffff00000001u64.to_le_bytes());
+        assert_eq!(&bytes[36..40], &3u32.to_le_bytes());
+        assert_eq!(&bytes[68..76], &0xffffffff00000000u64.to_le_bytes());
+        assert_eq!(Wtns::read(&bytes[..]).unwrap(), wtns);
+
+        let unreduced = Wtns {
+            witness: vec![prime.clone()],
+            prime,
+        };
+        assert!(unreduced.write(&mut vec![]).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let (r1cs, symbols) = mul_circuit();
+        let witness = |v: [u64; 4]| Wtns {
+            prime: r1cs.prime.clone(),
+            witness: v.map(BigUint::from).to_vec(),
+        };
+
+        assert!(r1cs.check(&witness([1, 6, 2, 3]), &symbols).is_ok());
+        assert_eq!(
+            r1cs.first_unsatisfied(&witness([1, 7, 2, 3]).witness),
+            Some(0)
+        );
+        let err = r1cs.check(&witness([1, 7, 2, 3]), &symbols).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "constraint 0 is not satisfied: main.x, main.y, main.out"
+        );
+        assert!(r1cs
+            .check(&witness([1, 6, 2, 3]), &Symbols::default())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_describe() {
+        let (r1cs, symbols) = mul_circuit();
+        let witness = [1u64, 7, 2, 3].map(BigUint::from);
+        assert_eq!(r1cs.unsatisfied(&witness).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(
+            r1cs.describe(0, &witness, &symbols),
+            "constraint 0: (-main.x) * (main.y) = (-main.out)\n  \
+             18446744069414584319 * 3 = 18446744069414584315, expected 18446744069414584314\n  \
+             main.x = 2\n  main.y = 3\n  main.out = 7\n"
+        );
+        assert_eq!(
+            r1cs.describe(0, &witness, &Symbols::default())
+                .lines()
+                .next()
+                .unwrap(),
+            "constraint 0: (-wire2) * (wire3) = (-wire1)"
+        );
+    }
+}
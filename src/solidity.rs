@@ -0,0 +1,282 @@
+//! Calldata for the snarkjs `verifier.sol`, without `snarkjs generatecall`, and the
+//! `Plonky2Verifier.sol` wrapper that decodes its public signals.
+
+use anyhow::{anyhow, ensure, Result};
+use keccak_hash::keccak;
+use num::BigUint;
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::PrimeField64;
+use plonky2::hash::hash_types::RichField;
+use plonky2::plonk::circuit_data::VerifierOnlyCircuitData;
+use plonky2::plonk::config::{GenericConfig, GenericHashOut};
+use serde_json::Value;
+
+use crate::verifier::{circom_verifier_key_hash, CircomVerifierKey};
+
+/// A Groth16 proof as `verifyProof` takes it. `G2` coordinates are `[c1, c0]`, the order of the
+/// BN254 pairing precompile, swapped from the snarkjs `proof.json`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolidityProof {
+    pub a: [BigUint; 2],
+    pub b: [[BigUint; 2]; 2],
+    pub c: [BigUint; 2],
+}
+
+impl SolidityProof {
+    /// Reads a snarkjs or rapidsnark `proof.json`.
+    pub fn from_snarkjs_json(proof_json: &str) -> Result<Self> {
+        let proof: Value = serde_json::from_str(proof_json)?;
+        let coordinate = |path: &[usize], name: &str| -> Result<BigUint> {
+            let value = path
+                .iter()
+                .try_fold(&proof[name], |v, i| v.get(i))
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("missing {}{:?} in proof", name, path))?;
+            value
+                .parse()
+                .map_err(|_| anyhow!("invalid {}{:?} in proof: {:?}", name, path, value))
+        };
+        Ok(Self {
+            a: [coordinate(&[0], "pi_a")?, coordinate(&[1], "pi_a")?],
+            b: [
+                [coordinate(&[0, 1], "pi_b")?, coordinate(&[0, 0], "pi_b")?],
+                [coordinate(&[1, 1], "pi_b")?, coordinate(&[1, 0], "pi_b")?],
+            ],
+            c: [coordinate(&[0], "pi_c")?, coordinate(&[1], "pi_c")?],
+        })
+    }
+}
+
+#[cfg(feature = "groth16")]
+impl From<&ark_groth16::Proof<ark_bn254::Bn254>> for SolidityProof {
+    fn from(proof: &ark_groth16::Proof<ark_bn254::Bn254>) -> Self {
+        use crate::groth16::to_biguint;
+        Self {
+            a: [to_biguint(proof.a.x), to_biguint(proof.a.y)],
+            b: [
+                [to_biguint(proof.b.x.c1), to_biguint(proof.b.x.c0)],
+                [to_biguint(proof.b.y.c1), to_biguint(proof.b.y.c0)],
+            ],
+            c: [to_biguint(proof.c.x), to_biguint(proof.c.y)],
+        }
+    }
+}
+
+fn push_uint256(out: &mut Vec<u8>, x: &BigUint) -> Result<()> {
+    let bytes = x.to_bytes_be();
+    ensure!(bytes.len() <= 32, "{} does not fit in a uint256", x);
+    out.resize(out.len() + 32 - bytes.len(), 0);
+    out.extend(bytes);
+    Ok(())
+}
+
+/// ABI encoded call of `verifyProof(uint[2],uint[2][2],uint[2],uint[N])` with the public
+/// signals of `circom_public_signals`.
+pub fn verify_proof_calldata(proof: &SolidityProof, public_signals: &[u64]) -> Result<Vec<u8>> {
+    let signature = format!(
+        "verifyProof(uint256[2],uint256[2][2],uint256[2],uint256[{}])",
+        public_signals.len()
+    );
+    let mut calldata = keccak(signature.as_bytes()).0[..4].to_vec();
+    for x in proof
+        .a
+        .iter()
+        .chain(proof.b.iter().flatten())
+        .chain(&proof.c)
+    {
+        push_uint256(&mut calldata, x)?;
+    }
+    for x in public_signals {
+        push_uint256(&mut calldata, &BigUint::from(*x))?;
+    }
+    Ok(calldata)
+}
+
+/// `Plonky2Verifier.sol`, a wrapper of the snarkjs `verifier.sol` of the Circom verifier for
+/// `verifier_only` with `verifier_key`. It checks that the public signals are for this verifier
+/// key and returns the `num_public_inputs` plonky2 public inputs as Goldilocks elements.
+pub fn generate_solidity_wrapper<
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    const D: usize,
+>(
+    verifier_only: &VerifierOnlyCircuitData<C, D>,
+    num_public_inputs: usize,
+    verifier_key: CircomVerifierKey,
+) -> Result<String> {
+    ensure!(
+        num_public_inputs > 0,
+        "the Solidity wrapper needs at least one public input"
+    );
+    let mut contract = std::fs::read_to_string("./src/template_plonky2_verifier.sol")?;
+
+    let uint64s = |x: &[F]| {
+        let values = x
+            .iter()
+            .map(|x| format!("uint64({})", x.to_canonical_u64()))
+            .collect::<Vec<_>>();
+        format!("[{}]", values.join(", "))
+    };
+    let sigma_cap = &verifier_only.constants_sigmas_cap.0;
+    let mut set_sigma_cap = vec![];
+    for (i, cap) in sigma_cap.iter().enumerate() {
+        set_sigma_cap.push(format!("        cap[{}] = {};", i, uint64s(&cap.to_vec())));
+    }
+
+    let (offset, check_verifier_key) = match verifier_key {
+        CircomVerifierKey::Constant => (
+            0,
+            "        // The verifier key is a constant of the Circom verifier.".to_owned(),
+        ),
+        CircomVerifierKey::Public => (
+            4 + 4 * sigma_cap.len(),
+            "        uint64[4] memory digest = circuitDigest();
+        uint64[4][$SIGMA_CAP_COUNT] memory cap = sigmaCap();
+        for (uint i = 0; i < 4; i++) {
+            require(input[i] == digest[i], \"wrong circuit digest\");
+        }
+        for (uint i = 0; i < $SIGMA_CAP_COUNT; i++) {
+            for (uint j = 0; j < 4; j++) {
+                require(input[4 + 4 * i + j] == cap[i][j], \"wrong sigma cap\");
+            }
+        }"
+            .to_owned(),
+        ),
+        CircomVerifierKey::Hash => (
+            4,
+            format!(
+                "        uint64[4] memory keyHash = {};
+        for (uint i = 0; i < 4; i++) {{
+            require(input[i] == keyHash[i], \"wrong verifier key hash\");
+        }}",
+                uint64s(&circom_verifier_key_hash(verifier_only).elements)
+            ),
+        ),
+    };
+
+    contract = contract.replace("$CHECK_VERIFIER_KEY", &check_verifier_key);
+    contract = contract.replace(
+        "$SET_CIRCUIT_DIGEST",
+        &format!(
+            "        digest = {};",
+            uint64s(&verifier_only.circuit_digest.to_vec())
+        ),
+    );
+    contract = contract.replace("$SET_SIGMA_CAP", &set_sigma_cap.join("\n"));
+    contract = contract.replace("$SIGMA_CAP_COUNT", &sigma_cap.len().to_string());
+    contract = contract.replace(
+        "$NUM_PUBLIC_SIGNALS",
+        &(offset + num_public_inputs).to_string(),
+    );
+    contract = contract.replace("$NUM_PUBLIC_INPUTS", &num_public_inputs.to_string());
+    contract = contract.replace("$PUBLIC_INPUTS_OFFSET", &offset.to_string());
+    Ok(contract)
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use plonky2::field::types::{Field, PrimeField64};
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::GenericConfig;
+
+    use crate::config::PoseidonBN128GoldilocksConfig;
+    use crate::fixtures::snarkjs_call;
+    use crate::solidity::{generate_solidity_wrapper, verify_proof_calldata, SolidityProof};
+    use crate::verifier::{
+        circom_public_signals, circom_verifier_key_hash, encode_hex, CircomVerifierKey,
+    };
+
+    #[test]
+    fn test_verify_proof_calldata() {
+        let proof = SolidityProof::from_snarkjs_json(
+            r#"{
+                "pi_a": ["1", "2", "1"],
+                "pi_b": [["3", "4"], ["5", "6"], ["1", "0"]],
+                "pi_c": ["7", "8", "1"],
+                "protocol": "groth16",
+                "curve": "bn128"
+            }"#,
+        )
+        .unwrap();
+        let values = |v: [u32; 2]| v.map(BigUint::from);
+        assert_eq!(proof.b, [values([4, 3]), values([6, 5])]);
+
+        let calldata = verify_proof_calldata(&proof, &[18446744069414584320]).unwrap();
+        assert_eq!(encode_hex(&calldata[..4]), "43753b4d");
+        assert_eq!(calldata.len(), 4 + 9 * 32);
+        let word = |i: usize| encode_hex(&calldata[4 + i * 32..4 + (i + 1) * 32]);
+        assert_eq!(word(0), format!("{:064x}", 1));
+        assert_eq!(word(2), format!("{:064x}", 4));
+        assert_eq!(word(5), format!("{:064x}", 5));
+        assert_eq!(word(8), format!("{:064x}", 18446744069414584320u64));
+
+        assert!(SolidityProof::from_snarkjs_json(r#"{"pi_a": ["1"]}"#).is_err());
+
+        // The arguments of the committed `snarkjs generatecall` output, in the same order.
+        let (call, proof_json, public_signals) = snarkjs_call().unwrap();
+        let proof = SolidityProof::from_snarkjs_json(&proof_json).unwrap();
+        let calldata = verify_proof_calldata(&proof, &public_signals).unwrap();
+        assert_eq!(calldata.len(), 4 + call.len() * 32);
+        let word = |i: usize| encode_hex(&calldata[4 + i * 32..4 + (i + 1) * 32]);
+        for (i, x) in call.iter().enumerate() {
+            assert_eq!(word(i), format!("{:064x}", x));
+        }
+    }
+
+    #[test]
+    fn test_generate_solidity_wrapper() -> anyhow::Result<()> {
+        const D: usize = 2;
+        type C = PoseidonBN128GoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+        let pi = builder.add_virtual_targets(2);
+        builder.register_public_inputs(&pi);
+        let data = builder.build::<C>();
+        let mut pw = PartialWitness::new();
+        pw.set_target(pi[0], F::from_canonical_u64(5));
+        pw.set_target(pi[1], F::from_canonical_u64(7));
+        let proof = data.prove(pw)?;
+        let vd = data.verifier_only;
+
+        // The wrapper reads the public signals in the order of the Circom verifier.
+        for verifier_key in [
+            CircomVerifierKey::Constant,
+            CircomVerifierKey::Public,
+            CircomVerifierKey::Hash,
+        ] {
+            let contract = generate_solidity_wrapper(&vd, 2, verifier_key)?;
+            assert!(!contract.contains('$'));
+            let signals = circom_public_signals(&proof, &vd, verifier_key);
+            assert!(contract.contains(&format!(
+                "uint constant NUM_PUBLIC_SIGNALS = {};",
+                signals.len()
+            )));
+            assert_eq!(signals[signals.len() - 2..], [5, 7]);
+            assert!(contract.contains(&format!(
+                "uint constant PUBLIC_INPUTS_OFFSET = {};",
+                signals.len() - 2
+            )));
+        }
+
+        let contract = generate_solidity_wrapper(&vd, 2, CircomVerifierKey::Constant)?;
+        assert!(contract.contains(&format!(
+            "uint64({})",
+            vd.circuit_digest.elements[0].to_canonical_u64()
+        )));
+
+        let contract = generate_solidity_wrapper(&vd, 2, CircomVerifierKey::Public)?;
+        assert!(contract.contains("require(input[i] == digest[i], \"wrong circuit digest\");"));
+
+        let contract = generate_solidity_wrapper(&vd, 2, CircomVerifierKey::Hash)?;
+        assert!(contract.contains(&format!(
+            "uint64({})",
+            circom_verifier_key_hash(&vd).elements[3].to_canonical_u64()
+        )));
+
+        assert!(generate_solidity_wrapper(&vd, 0, CircomVerifierKey::Constant).is_err());
+        Ok(())
+    }
+}
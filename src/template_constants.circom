@@ -76,6 +76,10 @@ function CIRCUIT_DIGEST() {
   $SET_CIRCUIT_DIGEST;
   return cd;
 }
+function VERIFIER_KEY_MODE() { return $VERIFIER_KEY_MODE; }
+function RANGE_CHECK_INPUTS() { return $RANGE_CHECK_INPUTS; }
+function HASHER() { return $HASHER; }
+function PUBLIC_INPUT_HASHER() { return $PUBLIC_INPUT_HASHER; }
 function SPONGE_RATE() { return 8; }
 function SPONGE_CAPACITY() { return 4; }
 function SPONGE_WIDTH() { return 12; }
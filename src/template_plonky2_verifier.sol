@@ -0,0 +1,71 @@
+// This file was generated by solidity.rs
+// SPDX-License-Identifier: GPL-3.0
+
+pragma solidity ^0.8.0;
+
+// The Groth16 verifier exported by `snarkjs zkey export solidityverifier`.
+interface IGroth16Verifier {
+    function verifyProof(
+        uint[2] memory a,
+        uint[2][2] memory b,
+        uint[2] memory c,
+        uint[$NUM_PUBLIC_SIGNALS] memory input
+    ) external view returns (bool);
+}
+
+// Verifies wrapped plonky2 proofs of one circuit and decodes their public inputs.
+contract Plonky2Verifier {
+    uint constant GOLDILOCKS_ORDER = 18446744069414584321;
+    uint constant NUM_PUBLIC_SIGNALS = $NUM_PUBLIC_SIGNALS;
+    uint constant NUM_PUBLIC_INPUTS = $NUM_PUBLIC_INPUTS;
+    // Index of the first plonky2 public input in the public signals.
+    uint constant PUBLIC_INPUTS_OFFSET = $PUBLIC_INPUTS_OFFSET;
+
+    IGroth16Verifier public immutable groth16Verifier;
+
+    constructor(IGroth16Verifier _groth16Verifier) {
+        groth16Verifier = _groth16Verifier;
+    }
+
+    function circuitDigest() public pure returns (uint64[4] memory digest) {
+$SET_CIRCUIT_DIGEST
+    }
+
+    function sigmaCap() public pure returns (uint64[4][$SIGMA_CAP_COUNT] memory cap) {
+$SET_SIGMA_CAP
+    }
+
+    // Reverts unless the public signals are for the pinned verifier key.
+    function checkVerifierKey(uint[NUM_PUBLIC_SIGNALS] calldata input) public pure {
+$CHECK_VERIFIER_KEY
+    }
+
+    // The i-th plonky2 public input, which must be a canonical Goldilocks element.
+    function publicInput(uint[NUM_PUBLIC_SIGNALS] calldata input, uint i) public pure returns (uint64) {
+        require(i < NUM_PUBLIC_INPUTS, "public input out of range");
+        uint value = input[PUBLIC_INPUTS_OFFSET + i];
+        require(value < GOLDILOCKS_ORDER, "public input is not a Goldilocks element");
+        return uint64(value);
+    }
+
+    function publicInputs(uint[NUM_PUBLIC_SIGNALS] calldata input)
+        public pure returns (uint64[NUM_PUBLIC_INPUTS] memory values)
+    {
+        for (uint i = 0; i < NUM_PUBLIC_INPUTS; i++) {
+            values[i] = publicInput(input, i);
+        }
+    }
+
+    // Verifies the proof and returns its plonky2 public inputs, or reverts.
+    function verifyProof(
+        uint[2] calldata a,
+        uint[2][2] calldata b,
+        uint[2] calldata c,
+        uint[NUM_PUBLIC_SIGNALS] calldata input
+    ) public view returns (uint64[NUM_PUBLIC_INPUTS] memory) {
+        checkVerifierKey(input);
+        uint64[NUM_PUBLIC_INPUTS] memory values = publicInputs(input);
+        require(groth16Verifier.verifyProof(a, b, c, input), "invalid proof");
+        return values;
+    }
+}
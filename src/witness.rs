@@ -0,0 +1,222 @@
+//! Witness generation for the generated Circom verifier with the WebAssembly witness calculator,
+//! instead of the C++ one.
+//!
+//! `WitnessCalculator` runs the witness calculator `circom --wasm` emits next to the `.r1cs`,
+//! so the witness always follows the compiled templates and their signal numbering. The result
+//! can be written in the snarkjs `.wtns` format with `snarkjs::Wtns`.
+//!
+//! This is not a native generator: the circuit still has to be compiled by circom, and nothing
+//! here mirrors the templates. Computing the witness straight from a plonky2 proof would need
+//! every intermediate signal of every template, in circom's numbering.
+
+use anyhow::{anyhow, ensure, Result};
+use num::BigUint;
+use serde_json::Value;
+
+use crate::poseidon_bn128::GOLDILOCKS_ORDER;
+#[cfg(feature = "wasm-witness")]
+use crate::snarkjs::Wtns;
+
+/// Flattens a Circom input JSON, such as the output of `generate_proof_base64`, into the values
+/// of each input signal. Multidimensional arrays are flattened row by row, like Circom does.
+pub fn flatten_inputs(input_json: &str) -> Result<Vec<(String, Vec<BigUint>)>> {
+    fn flatten(value: &Value, out: &mut Vec<BigUint>) -> Result<()> {
+        match value {
+            Value::Array(values) => {
+                for v in values {
+                    flatten(v, out)?;
+                }
+            }
+            Value::String(s) => out.push(
+                s.parse()
+                    .map_err(|_| anyhow!("invalid input value {:?}", s))?,
+            ),
+            Value::Number(n) => out.push(
+                n.as_u64()
+                    .ok_or_else(|| anyhow!("invalid input value {}", n))?
+                    .into(),
+            ),
+            _ => return Err(anyhow!("invalid input value {}", value)),
+        }
+        Ok(())
+    }
+
+    let input: serde_json::Map<String, Value> = serde_json::from_str(input_json)?;
+    input
+        .iter()
+        .map(|(name, value)| {
+            let mut values = vec![];
+            flatten(value, &mut values)?;
+            Ok((name.clone(), values))
+        })
+        .collect()
+}
+
+/// Checks that every value of a Circom input JSON is a canonical Goldilocks element, as the
+/// range checks of the generated verifier require.
+pub fn check_canonical_inputs(input_json: &str) -> Result<()> {
+    for (name, values) in flatten_inputs(input_json)? {
+        for (i, value) in values.iter().enumerate() {
+            ensure!(
+                *value < BigUint::from(GOLDILOCKS_ORDER),
+                "input {}[{}] = {} is not a canonical Goldilocks element",
+                name,
+                i,
+                value
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 64-bit FNV-1a hash, which the Circom witness calculator uses to look up input signals.
+fn fnv_hash(name: &str) -> u64 {
+    name.bytes().fold(0xcbf29ce484222325, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Computes witnesses with the WebAssembly witness calculator of a circuit, such as
+/// `plonky2_js/plonky2.wasm` from `circom plonky2.circom --wasm`.
+#[cfg(feature = "wasm-witness")]
+pub struct WitnessCalculator {
+    instance: wasmer::Instance,
+    n32: usize,
+    prime: BigUint,
+}
+
+#[cfg(feature = "wasm-witness")]
+impl WitnessCalculator {
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        use wasmer::{imports, Function, Module, RuntimeError, Store};
+
+        let store = Store::default();
+        let module = Module::from_file(&store, path)?;
+        let import_object = imports! {
+            "runtime" => {
+                "exceptionHandler" => Function::new_native(&store, |code: i32| -> Result<(), RuntimeError> {
+                    Err(RuntimeError::new(format!("witness calculator error {}", code)))
+                }),
+                "showSharedRWMemory" => Function::new_native(&store, || {}),
+                "printErrorMessage" => Function::new_native(&store, || {}),
+                "writeBufferMessage" => Function::new_native(&store, || {}),
+            },
+        };
+        let instance = wasmer::Instance::new(&module, &import_object)?;
+
+        let mut calculator = Self {
+            instance,
+            n32: 0,
+            prime: BigUint::default(),
+        };
+        calculator.n32 = calculator.function::<(), i32>("getFieldNumLen32")?.call()? as usize;
+        calculator.function::<(), ()>("getRawPrime")?.call()?;
+        calculator.prime = calculator.read_shared_memory()?;
+        Ok(calculator)
+    }
+
+    /// The field modulus of the circuit, the BN254 scalar field order for `plonky2.circom`.
+    pub fn prime(&self) -> &BigUint {
+        &self.prime
+    }
+
+    fn function<Args, Rets>(&self, name: &str) -> Result<wasmer::NativeFunc<Args, Rets>>
+    where
+        Args: wasmer::WasmTypeList,
+        Rets: wasmer::WasmTypeList,
+    {
+        Ok(self.instance.exports.get_function(name)?.native()?)
+    }
+
+    fn read_shared_memory(&self) -> Result<BigUint> {
+        let read = self.function::<i32, i32>("readSharedRWMemory")?;
+        let words = (0..self.n32)
+            .map(|i| Ok(read.call(i as i32)? as u32))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(BigUint::from_slice(&words))
+    }
+
+    /// Computes the full witness for the inputs in `input_json`, see `flatten_inputs`.
+    pub fn calculate_witness(&self, input_json: &str) -> Result<Wtns> {
+        self.function::<i32, ()>("init")?.call(1)?;
+
+        let write = self.function::<(i32, i32), ()>("writeSharedRWMemory")?;
+        let input_size = self.function::<(i32, i32), i32>("getInputSignalSize")?;
+        let set_input = self.function::<(i32, i32, i32), ()>("setInputSignal")?;
+        for (name, values) in flatten_inputs(input_json)? {
+            let h = fnv_hash(&name);
+            let (msb, lsb) = ((h >> 32) as i32, h as u32 as i32);
+            let size = input_size.call(msb, lsb)?;
+            ensure!(size >= 0, "circuit has no input signal {}", name);
+            ensure!(
+                size as usize == values.len(),
+                "input signal {} has {} values, expected {}",
+                name,
+                values.len(),
+                size
+            );
+            for (i, value) in values.iter().enumerate() {
+                let mut words = (value % &self.prime).to_u32_digits();
+                words.resize(self.n32, 0);
+                for (j, word) in words.into_iter().enumerate() {
+                    write.call(j as i32, word as i32)?;
+                }
+                set_input.call(msb, lsb, i as i32)?;
+            }
+        }
+
+        let witness_size = self.function::<(), i32>("getWitnessSize")?.call()?;
+        let get_witness = self.function::<i32, ()>("getWitness")?;
+        let witness = (0..witness_size)
+            .map(|i| {
+                get_witness.call(i)?;
+                self.read_shared_memory()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Wtns {
+            prime: self.prime.clone(),
+            witness,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use crate::witness::{check_canonical_inputs, flatten_inputs, fnv_hash};
+
+    #[test]
+    fn test_fnv_hash() {
+        assert_eq!(fnv_hash("in"), 0x08b73807b55c4bbe);
+        assert_eq!(fnv_hash("wires_cap"), 0x73c87dfca8668b2a);
+        assert_eq!(fnv_hash("public_inputs"), 0x45f79ca9d64a9d60);
+    }
+
+    #[test]
+    fn test_flatten_inputs() {
+        let inputs = flatten_inputs(
+            r#"{"a": [["1", "2"], ["3", "4"]], "b": "18446744069414584320", "c": 5}"#,
+        )
+        .unwrap();
+        let values = |v: &[u64]| v.iter().map(|&x| BigUint::from(x)).collect::<Vec<_>>();
+        assert_eq!(
+            inputs,
+            vec![
+                ("a".to_string(), values(&[1, 2, 3, 4])),
+                ("b".to_string(), values(&[18446744069414584320])),
+                ("c".to_string(), values(&[5])),
+            ]
+        );
+        assert!(flatten_inputs(r#"{"a": ["x"]}"#).is_err());
+    }
+
+    #[test]
+    fn test_check_canonical_inputs() {
+        check_canonical_inputs(r#"{"a": [["0", "18446744069414584320"]], "b": "4294967295"}"#)
+            .unwrap();
+        let err = check_canonical_inputs(r#"{"a": ["1", "18446744069414584321"]}"#).unwrap_err();
+        assert!(err.to_string().contains("a[1]"));
+        assert!(check_canonical_inputs(r#"{"a": "18446744073709551616"}"#).is_err());
+    }
+}